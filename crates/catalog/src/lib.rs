@@ -6,16 +6,68 @@ pub mod catalog {
     use codec::{Decode, Encode};
     use ink::{
         env::{
+            call::{build_call, ExecutionInput, Selector},
             hash::{HashOutput, Keccak256},
-            hash_bytes, DefaultEnvironment, Environment as InkEnv,
+            hash_bytes,
         },
-        prelude::{vec, vec::Vec},
-        storage::Mapping,
+        prelude::{string::String, vec::Vec},
+        storage::{Lazy, Mapping},
     };
 
     pub type HashId = <Keccak256 as HashOutput>::Type;
-    type Workers = Mapping<AccountId, u32>;
-    type JobMetaData = Mapping<AccountId, Vec<(HashId, <DefaultEnvironment as InkEnv>::Balance)>>;
+    type Workers = Mapping<AccountId, WorkerProfile>;
+    type Stakes = Mapping<AccountId, Stake>;
+    type Jobs = Mapping<HashId, JobRecord>;
+    type JobIndex = Mapping<(AccountId, u64), HashId>;
+    type OpenJobs = Mapping<u64, HashId>;
+    type OpenJobPositions = Mapping<HashId, u64>;
+    type WorkerIndex = Mapping<u64, AccountId>;
+    type WorkerPositions = Mapping<AccountId, u64>;
+    type Nonces = Mapping<AccountId, u64>;
+    type Commitments = Mapping<(HashId, AccountId), HashId>;
+    type Reveals = Mapping<(HashId, AccountId), HashId>;
+    type Disputes = Mapping<HashId, Dispute>;
+    type Reputations = Mapping<AccountId, Reputation>;
+    type Auctions = Mapping<HashId, Auction>;
+    type SealedBids = Mapping<(HashId, AccountId), HashId>;
+    type Bids = Mapping<(HashId, AccountId), Balance>;
+    type JobTokens = Mapping<HashId, AccountId>;
+    type TokenFees = Mapping<AccountId, Balance>;
+    type Allowlists = Mapping<HashId, Vec<AccountId>>;
+    type JobMilestones = Mapping<HashId, Milestones>;
+    type ActiveJobs = Mapping<AccountId, u32>;
+
+    /// Time in milliseconds a job stays open before its requester can reclaim the escrow.
+    pub const DEFAULT_JOB_TIMEOUT: Timestamp = 10 * 60 * 1000;
+    /// Minimum balance a worker has to lock before it can accept jobs.
+    pub const MIN_WORKER_STAKE: Balance = 10_000;
+    /// Time in milliseconds a worker's stake stays locked after it unregisters.
+    pub const UNBONDING_PERIOD: Timestamp = 24 * 60 * 60 * 1000;
+    /// Time in milliseconds the requester has to pay or dispute a committed result.
+    pub const CLAIM_WINDOW: Timestamp = 60 * 60 * 1000;
+    /// Time in milliseconds replicas have to reveal their results once all of them committed.
+    pub const REVEAL_WINDOW: Timestamp = 10 * 60 * 1000;
+    /// Upper bound on how many workers can be asked to run the same job.
+    pub const MAX_REPLICAS: u8 = 16;
+    /// Balance a requester has to lock to dispute a job, forfeited to the workers if they win.
+    pub const DISPUTE_BOND: Balance = 1_000;
+    /// Number of matching votes from re-executing workers that settles a dispute.
+    pub const DISPUTE_QUORUM: u8 = 3;
+    /// Upper bound on how many items a paginated query returns at once.
+    pub const MAX_PAGE_SIZE: u32 = 100;
+    /// Upper bound on the protocol fee, in basis points, the owner can charge on payouts.
+    pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+    /// Upper bound on how many sealed bids a single auction accepts.
+    pub const MAX_BIDS: u8 = 32;
+    /// Selector of `PSP22::approve`, which requesters call on a token before paying for a job
+    /// with it.
+    pub const PSP22_APPROVE_SELECTOR: [u8; 4] = [0xb2, 0x0f, 0x1b, 0xbd];
+    /// Upper bound on how many workers a job can be restricted to.
+    pub const MAX_ALLOWED_WORKERS: u8 = 32;
+    /// Upper bound on how many milestones a job's escrow can be released in.
+    pub const MAX_MILESTONES: u8 = 64;
+    const PSP22_TRANSFER_SELECTOR: [u8; 4] = [0xdb, 0x20, 0xf9, 0xf5];
+    const PSP22_TRANSFER_FROM_SELECTOR: [u8; 4] = [0x54, 0xb3, 0xc7, 0x6e];
 
     #[derive(Debug, PartialEq, Eq, Encode, Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -23,266 +75,4671 @@ pub mod catalog {
         AccountNotFoud,
         FailedTransfer,
         JobNotFound,
+        JobAlreadyAccepted,
+        DeadlineNotReached,
+        DeadlineExpired,
+        JobAlreadyExists,
+        NotJobRequester,
+        NotAssignedWorker,
+        InvalidJobState,
+        InsufficientStake,
+        WorkerNotStaked,
+        WorkerUnbonding,
+        UnbondingNotFinished,
+        NotAuthorizedToSlash,
+        WorkerNotRegistered,
+        InvalidReplicas,
+        WorkerAlreadyAccepted,
+        ResultAlreadyCommitted,
+        ResultAlreadyRevealed,
+        InvalidReveal,
+        RequirementsNotMet,
+        NotOwner,
+        ContractPaused,
+        UpgradeFailed,
+        InvalidFee,
+        NotTreasury,
+        InsufficientBond,
+        DisputeNotFound,
+        NotArbiter,
+        NotEligibleToVote,
+        AlreadyVoted,
+        BidAlreadySubmitted,
+        TooManyBids,
+        InvalidBid,
+        InvalidPayment,
+        TokenTransferFailed,
+        InvalidAllowlist,
+        NotAllowedWorker,
+        InvalidMilestones,
+        NoMilestoneLeft,
+        MilestoneNotReported,
+        WorkerHasOpenJobs,
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct WorkerRegistered {
-        pub who: AccountId,
-        pub val: u32,
+    /// Error returned by PSP22 token contracts.
+    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct JobRequestSubmitted {
-        pub who: AccountId,
-        pub id: HashId,
+    #[derive(Debug, Encode, Decode, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum ValueType {
+        I32,
+        I64,
+        F32,
+        F64,
+        V128,
     }
 
-    impl JobRequestSubmitted {
-        pub fn id(&self) -> HashId {
-            self.id
-        }
+    /// Capabilities a worker advertises to requesters when it registers with the catalog.
+    #[derive(Debug, Encode, Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct WorkerProfile {
+        value_types: Vec<ValueType>,
+        max_memory: u64,
+        max_fuel: u64,
+        price_per_fuel: Balance,
+        peer_id: Vec<u8>,
+        version: String,
     }
 
-    #[derive(Debug)]
-    #[ink(event)]
-    pub struct PaidWorker {
-        pub destination: AccountId,
-    }
+    impl WorkerProfile {
+        pub fn new(
+            value_types: Vec<ValueType>,
+            max_memory: u64,
+            max_fuel: u64,
+            price_per_fuel: Balance,
+            peer_id: Vec<u8>,
+            version: String,
+        ) -> Self {
+            Self {
+                value_types,
+                max_memory,
+                max_fuel,
+                price_per_fuel,
+                peer_id,
+                version,
+            }
+        }
 
-    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
-    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-    pub struct JobRequest {
-        id: HashId,
-    }
+        pub fn value_types(&self) -> &[ValueType] {
+            &self.value_types
+        }
 
-    impl JobRequest {
-        pub fn new(code: &[u8], params: &Vec<Vec<u8>>) -> Self {
-            let id = Self::hash(code, params);
+        /// Maximum linear memory, in bytes, the worker allows a job to allocate.
+        pub fn max_memory(&self) -> u64 {
+            self.max_memory
+        }
 
-            Self { id }
+        pub fn max_fuel(&self) -> u64 {
+            self.max_fuel
         }
 
-        pub fn id(&self) -> HashId {
-            self.id
+        pub fn price_per_fuel(&self) -> Balance {
+            self.price_per_fuel
+        }
+
+        pub fn peer_id(&self) -> &[u8] {
+            &self.peer_id
+        }
+
+        pub fn version(&self) -> &str {
+            &self.version
+        }
+
+        /// Returns true if the worker can take params and results of every given type.
+        pub fn supports(&self, value_types: &[ValueType]) -> bool {
+            value_types.iter().all(|t| self.value_types.contains(t))
         }
 
-        pub fn hash(code: &[u8], params: &Vec<Vec<u8>>) -> HashId {
-            code.encode_to(&mut params.encode());
-            hash(code)
+        /// Returns true if the worker is able to run a job with the given requirements.
+        pub fn meets(&self, requirements: &JobRequirements) -> bool {
+            self.supports(&requirements.value_types)
+                && self.max_memory >= requirements.memory
+                && self.max_fuel >= requirements.fuel
         }
     }
 
-    #[ink(storage)]
-    pub struct Catalog {
-        workers: Workers,
-        job_metadata: JobMetaData,
+    #[derive(Debug, Encode, Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Stake {
+        amount: Balance,
+        unbonding_at: Option<Timestamp>,
     }
 
-    impl Default for Catalog {
-        fn default() -> Self {
-            Self::new()
+    impl Stake {
+        pub fn amount(&self) -> Balance {
+            self.amount
+        }
+
+        pub fn unbonding_at(&self) -> Option<Timestamp> {
+            self.unbonding_at
         }
     }
 
-    impl Catalog {
-        #[ink(constructor)]
-        pub fn new() -> Self {
+    /// Resources a job needs from the worker that runs it.
+    #[derive(Debug, Default, Encode, Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct JobRequirements {
+        value_types: Vec<ValueType>,
+        memory: u64,
+        fuel: u64,
+    }
+
+    impl JobRequirements {
+        pub fn new(value_types: Vec<ValueType>, memory: u64, fuel: u64) -> Self {
             Self {
-                workers: Mapping::new(),
-                job_metadata: Mapping::new(),
+                value_types,
+                memory,
+                fuel,
             }
         }
 
-        #[ink(message)]
-        pub fn get_worker(&self) -> u32 {
-            let caller = self.env().caller();
+        pub fn value_types(&self) -> &[ValueType] {
+            &self.value_types
+        }
 
-            self.workers.get(caller).unwrap_or(0)
+        /// Linear memory, in bytes, the job needs to allocate.
+        pub fn memory(&self) -> u64 {
+            self.memory
         }
 
-        #[ink(message)]
-        pub fn register_worker(&mut self, val: u32) {
-            let caller = self.env().caller();
-            self.workers.insert(caller, &val);
+        pub fn fuel(&self) -> u64 {
+            self.fuel
+        }
+    }
 
-            self.env().emit_event(WorkerRegistered { who: caller, val });
+    /// Track record of a worker, updated by the catalog as its jobs move through their lifecycle.
+    #[derive(Debug, Default, Encode, Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Reputation {
+        jobs_completed: u32,
+        jobs_abandoned: u32,
+        disputes_lost: u32,
+        results_committed: u32,
+        total_time_to_result: Timestamp,
+    }
+
+    impl Reputation {
+        pub fn jobs_completed(&self) -> u32 {
+            self.jobs_completed
         }
 
-        #[ink(message, payable)]
-        pub fn submit_job_request(&mut self, job_request: JobRequest) {
-            let who = self.env().caller();
-            let id = job_request.id();
-            let value = self.env().transferred_value();
+        pub fn jobs_abandoned(&self) -> u32 {
+            self.jobs_abandoned
+        }
 
-            let metadatas = if let Some(mut metadatas) = self.job_metadata.get(who) {
-                metadatas.push((id, value));
-                metadatas
-            } else {
-                vec![(id, value)]
-            };
+        pub fn disputes_lost(&self) -> u32 {
+            self.disputes_lost
+        }
 
-            self.job_metadata.insert(who, &metadatas);
-            self.env().emit_event(JobRequestSubmitted { who, id });
+        /// Jobs the worker was slashed for, either by abandoning them or by losing a dispute.
+        pub fn failures(&self) -> u32 {
+            self.jobs_abandoned.saturating_add(self.disputes_lost)
         }
 
-        #[ink(message)]
-        pub fn pay_worker(
-            &mut self,
-            destination: AccountId,
-            job_id: HashId,
-        ) -> Result<(), CatalogError> {
-            let caller = self.env().caller();
-            let mut who = self
-                .job_metadata
-                .get(caller)
-                .ok_or(CatalogError::AccountNotFoud)?;
+        /// Average time in milliseconds between a job being accepted and the worker committing
+        /// its result, or `None` if it never committed one.
+        pub fn average_time_to_result(&self) -> Option<Timestamp> {
+            self.total_time_to_result
+                .checked_div(Timestamp::from(self.results_committed))
+        }
+    }
 
-            let value = who.iter().find(|(id, _)| id == &job_id).map(|(_, val)| val);
+    #[derive(Debug, Encode, Decode, PartialEq, Eq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum JobState {
+        Submitted,
+        Accepted,
+        Revealing,
+        ResultCommitted { result_hash: HashId },
+        Paid,
+        Cancelled,
+        Disputed,
+        Bidding,
+    }
 
-            if let Some(val) = value {
-                self.env()
-                    .transfer(destination, *val)
-                    .map_err(|_| CatalogError::FailedTransfer)?;
-                who.retain(|(id, _)| id != &job_id);
-                self.job_metadata.insert(caller, &who);
-                self.env().emit_event(PaidWorker { destination });
-            } else {
-                return Err(CatalogError::JobNotFound);
-            }
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct JobRecord {
+        requester: AccountId,
+        value: Balance,
+        timeout: Timestamp,
+        deadline: Timestamp,
+        replicas: u8,
+        requirements: JobRequirements,
+        workers: Vec<AccountId>,
+        state: JobState,
+    }
 
-            Ok(())
+    impl JobRecord {
+        pub fn requester(&self) -> AccountId {
+            self.requester
+        }
+
+        pub fn value(&self) -> Balance {
+            self.value
+        }
+
+        pub fn deadline(&self) -> Timestamp {
+            self.deadline
+        }
+
+        pub fn replicas(&self) -> u8 {
+            self.replicas
+        }
+
+        pub fn requirements(&self) -> &JobRequirements {
+            &self.requirements
+        }
+
+        pub fn workers(&self) -> &[AccountId] {
+            &self.workers
+        }
+
+        pub fn state(&self) -> &JobState {
+            &self.state
+        }
+
+        /// Number of replicas that have to agree on a result before the job can be settled.
+        pub fn quorum(&self) -> usize {
+            usize::from(self.replicas) / 2 + 1
         }
     }
 
-    fn hash(data: &[u8]) -> HashId {
-        let mut output = HashId::default();
-        hash_bytes::<Keccak256>(data, &mut output);
-        output
+    /// Open dispute over a job's result. A verdict of `None` sides with the requester, while
+    /// `Some(result_hash)` sides with the workers that revealed that hash. Voters commit sealed
+    /// ballots until `voting_ends` and reveal them until `reveal_ends`.
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Dispute {
+        bond: Balance,
+        voting_ends: Timestamp,
+        reveal_ends: Timestamp,
+        ballots: Vec<(AccountId, HashId)>,
+        votes: Vec<(AccountId, Option<HashId>)>,
     }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::{
-            env::{
-                pay_with_call,
-                test::{recorded_events, EmittedEvent},
-            },
-            primitives::AccountId,
-            scale::Decode,
-        };
+    impl Dispute {
+        pub fn bond(&self) -> Balance {
+            self.bond
+        }
 
-        impl JobRequest {
-            fn test(code: Vec<u8>) -> Self {
-                let params: Vec<Vec<u8>> = vec![];
+        pub fn voting_ends(&self) -> Timestamp {
+            self.voting_ends
+        }
 
-                Self::new(code.as_slice(), &params)
-            }
+        pub fn reveal_ends(&self) -> Timestamp {
+            self.reveal_ends
         }
 
-        #[ink::test]
-        fn register_worker_emits_event() {
-            let val = 10;
-            let who = AccountId::from([1; 32]);
-            let mut catalog = Catalog::default();
+        pub fn ballots(&self) -> &[(AccountId, HashId)] {
+            &self.ballots
+        }
 
-            catalog.register_worker(val);
+        pub fn votes(&self) -> &[(AccountId, Option<HashId>)] {
+            &self.votes
+        }
+    }
 
-            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
-            let worker_set_event =
-                <WorkerRegistered as Decode>::decode(&mut emitted_events[0].data.as_slice())
-                    .unwrap();
+    /// Sealed-bid auction for a job. Workers bid until `bidding_ends` and reveal their bids until
+    /// `reveal_ends`, after which the lowest revealed bid wins the job.
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Auction {
+        bidding_ends: Timestamp,
+        reveal_ends: Timestamp,
+        bidders: Vec<AccountId>,
+    }
 
-            assert_eq!(emitted_events.len(), 1);
-            assert_eq!(worker_set_event.val, val);
-            assert_eq!(worker_set_event.who, who);
+    impl Auction {
+        pub fn bidding_ends(&self) -> Timestamp {
+            self.bidding_ends
         }
 
-        #[ink::test]
-        fn submit_job_request_emits_event() {
-            let who = AccountId::from([1; 32]);
-            let mut catalog = Catalog::default();
-            let code = vec![1, 2, 3, 4];
-            let value = 100;
+        pub fn reveal_ends(&self) -> Timestamp {
+            self.reveal_ends
+        }
 
-            let job_request = JobRequest::test(code.clone());
+        pub fn bidders(&self) -> &[AccountId] {
+            &self.bidders
+        }
+    }
 
-            pay_with_call!(catalog.submit_job_request(job_request.clone()), value);
+    /// Schedule for releasing a job's escrow in equal parts as its worker reports progress. The
+    /// last milestone is the job's result, which is paid out like that of any other job.
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Milestones {
+        count: u8,
+        amount: Balance,
+        reported: u8,
+        released: u8,
+    }
 
-            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
-            let job_submitted_event =
-                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[0].data.as_slice())
-                    .unwrap();
-            let metadatas = catalog.job_metadata.get(who).unwrap();
+    impl Milestones {
+        pub fn count(&self) -> u8 {
+            self.count
+        }
 
-            assert_eq!(job_submitted_event.who, who);
-            assert_eq!(job_submitted_event.id, job_request.id());
-            assert_eq!(metadatas[0].0, job_request.id());
-            assert_eq!(metadatas[0].1, value);
+        /// Escrow released for each milestone.
+        pub fn amount(&self) -> Balance {
+            self.amount
         }
 
-        #[ink::test]
-        fn submit_job_request_appends_to_existing_jobs() {
-            let who = AccountId::from([1; 32]);
-            let mut catalog = Catalog::default();
-            let value = 100;
+        pub fn reported(&self) -> u8 {
+            self.reported
+        }
 
-            let code_1 = vec![1, 2, 3, 4];
-            let code_2 = vec![1, 2, 3, 5];
+        pub fn released(&self) -> u8 {
+            self.released
+        }
+    }
 
-            let job_1_request = JobRequest::test(code_1.clone());
-            let job_2_request = JobRequest::test(code_2.clone());
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct OwnershipTransferred {
+        #[ink(topic)]
+        pub previous_owner: AccountId,
+        #[ink(topic)]
+        pub new_owner: AccountId,
+    }
 
-            pay_with_call!(catalog.submit_job_request(job_1_request.clone()), value);
-            pay_with_call!(catalog.submit_job_request(job_2_request.clone()), value);
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        pub who: AccountId,
+    }
 
-            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        pub who: AccountId,
+    }
 
-            let job_submitted_event_1 =
-                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[0].data.as_slice())
-                    .unwrap();
-            let job_submitted_event_2 =
-                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[1].data.as_slice())
-                    .unwrap();
-            let jobs = catalog.job_metadata.get(who).unwrap();
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct CodeUpgraded {
+        pub code_hash: Hash,
+    }
 
-            assert_eq!(job_submitted_event_1.id, job_1_request.id());
-            assert_eq!(job_submitted_event_2.id, job_2_request.id());
+    /// Account that collects the protocol fee, the fee itself and what has been collected so far.
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Treasury {
+        account: AccountId,
+        fee_bps: u16,
+        balance: Balance,
+    }
 
-            assert_eq!(jobs.len(), 2);
-            assert_eq!(jobs[0].0, job_1_request.id());
-            assert_eq!(jobs[0].1, value);
+    impl Treasury {
+        pub fn account(&self) -> AccountId {
+            self.account
+        }
 
-            assert_eq!(jobs[1].0, job_2_request.id());
-            assert_eq!(jobs[1].1, value);
+        pub fn fee_bps(&self) -> u16 {
+            self.fee_bps
         }
 
-        #[ink::test]
-        fn pay_worker_emits_event_and_updates_storage() {
-            let requester = AccountId::from([1; 32]);
-            let worker = AccountId::from([3; 32]);
-            let mut catalog = Catalog::default();
+        pub fn balance(&self) -> Balance {
+            self.balance
+        }
 
-            let job_request = JobRequest::test(vec![1, 2, 3, 4]);
-            let value = 100;
-            let job_id = job_request.id();
+        /// Fee charged on a payout of `amount`.
+        pub fn fee(&self, amount: Balance) -> Balance {
+            amount.saturating_mul(Balance::from(self.fee_bps)) / 10_000
+        }
+    }
 
-            catalog
-                .job_metadata
-                .insert(requester, &vec![(job_id, value)]);
-            catalog.pay_worker(worker, job_id).unwrap();
+    /// One page of a paginated query, along with the cursor to pass to fetch the next one.
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Page<T> {
+        items: Vec<T>,
+        next_cursor: Option<u64>,
+    }
 
-            let metadata_after = catalog.job_metadata.get(requester).unwrap();
+    impl<T> Page<T> {
+        pub fn items(&self) -> &[T] {
+            &self.items
+        }
 
-            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+        pub fn into_items(self) -> Vec<T> {
+            self.items
+        }
 
-            let paid_event =
-                <PaidWorker as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+        /// Cursor of the next page, or `None` if this is the last one.
+        pub fn next_cursor(&self) -> Option<u64> {
+            self.next_cursor
+        }
+    }
 
-            assert_eq!(paid_event.destination, worker);
-            assert!(metadata_after.len() == 0);
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct WorkerRegistered {
+        #[ink(topic)]
+        pub who: AccountId,
+        pub stake: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct WorkerProfileUpdated {
+        #[ink(topic)]
+        pub who: AccountId,
+        pub profile: WorkerProfile,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct WorkerUnbonding {
+        #[ink(topic)]
+        pub who: AccountId,
+        pub unbonding_at: Timestamp,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct StakeWithdrawn {
+        #[ink(topic)]
+        pub who: AccountId,
+        pub value: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct WorkerSlashed {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub value: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobRequestSubmitted {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub nonce: u64,
+        pub value: Balance,
+        pub deadline: Timestamp,
+        pub timeout: Timestamp,
+        pub replicas: u8,
+        pub requirements: JobRequirements,
+        pub bidding_ends: Option<Timestamp>,
+        pub token: Option<AccountId>,
+        pub allowed_workers: Vec<AccountId>,
+        pub milestones: u8,
+    }
+
+    impl JobRequestSubmitted {
+        pub fn id(&self) -> HashId {
+            self.id
+        }
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobAccepted {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+    }
+
+    /// Emitted once every replica slot of a job has been taken.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobAssigned {
+        #[ink(topic)]
+        pub id: HashId,
+        pub workers: Vec<AccountId>,
+        pub deadline: Timestamp,
+    }
+
+    /// Emitted when a requester restricts a job to the workers it picked.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct WorkersAssigned {
+        #[ink(topic)]
+        pub id: HashId,
+        pub workers: Vec<AccountId>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BidSubmitted {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct BidRevealed {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub price: Balance,
+    }
+
+    /// Emitted once an auction is closed. `winner` is `None` if no valid bid was revealed, in
+    /// which case the job is cancelled and the requester refunded.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct AuctionClosed {
+        #[ink(topic)]
+        pub id: HashId,
+        pub winner: Option<AccountId>,
+        pub price: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ProgressReported {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub milestone: u8,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct MilestonePaid {
+        #[ink(topic)]
+        pub id: HashId,
+        pub milestone: u8,
+        pub value: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobResultCommitted {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub commitment: HashId,
+        pub committed_at: Timestamp,
+    }
+
+    /// Emitted once every replica has committed, opening the reveal window until `deadline`.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct RevealStarted {
+        #[ink(topic)]
+        pub id: HashId,
+        pub deadline: Timestamp,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobResultRevealed {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub result_hash: HashId,
+    }
+
+    /// Emitted once a quorum of replicas revealed `result_hash`, opening the claim window until
+    /// `deadline`.
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ResultAgreed {
+        #[ink(topic)]
+        pub id: HashId,
+        pub result_hash: HashId,
+        pub deadline: Timestamp,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobDisputed {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DisputeBallotCast {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DisputeVoted {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub verdict: Option<HashId>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct DisputeResolved {
+        #[ink(topic)]
+        pub id: HashId,
+        pub verdict: Option<HashId>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct ResultOutlier {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct JobCancelled {
+        #[ink(topic)]
+        pub who: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub value: Balance,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct PaidWorker {
+        #[ink(topic)]
+        pub destination: AccountId,
+        #[ink(topic)]
+        pub id: HashId,
+        pub value: Balance,
+        pub fee: Balance,
+        pub token: Option<AccountId>,
+    }
+
+    #[derive(Debug)]
+    #[ink(event)]
+    pub struct TreasuryWithdrawn {
+        #[ink(topic)]
+        pub who: AccountId,
+        pub value: Balance,
+        pub token: Option<AccountId>,
+    }
+
+    #[derive(Debug, Encode, Decode, PartialEq, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct JobRequest {
+        code_hash: HashId,
+        params_hash: HashId,
+        func_name: Vec<u8>,
+        timeout: Timestamp,
+        replicas: u8,
+        requirements: JobRequirements,
+        bidding_window: Option<Timestamp>,
+        token: Option<(AccountId, Balance)>,
+        allowed_workers: Vec<AccountId>,
+        milestones: u8,
+    }
+
+    impl JobRequest {
+        pub fn new(code: &[u8], params: &Vec<Vec<u8>>, func_name: &[u8]) -> Self {
+            Self {
+                code_hash: hash(code),
+                params_hash: hash(&params.encode()),
+                func_name: func_name.to_vec(),
+                timeout: DEFAULT_JOB_TIMEOUT,
+                replicas: 1,
+                requirements: JobRequirements::default(),
+                bidding_window: None,
+                token: None,
+                allowed_workers: Vec::new(),
+                milestones: 1,
+            }
+        }
+
+        /// Overrides how long, in milliseconds, the job stays open before it can be cancelled.
+        pub fn with_timeout(mut self, timeout: Timestamp) -> Self {
+            self.timeout = timeout;
+            self
+        }
+
+        /// Sets how many distinct workers have to run the job.
+        pub fn with_replicas(mut self, replicas: u8) -> Self {
+            self.replicas = replicas;
+            self
+        }
+
+        /// Restricts the job to workers whose profile meets `requirements`.
+        pub fn with_requirements(mut self, requirements: JobRequirements) -> Self {
+            self.requirements = requirements;
+            self
+        }
+
+        /// Auctions the job instead of handing it to the first worker that accepts it. Workers
+        /// submit sealed bids for `bidding_window` milliseconds, and the transferred value is the
+        /// most the requester is willing to pay.
+        pub fn with_auction(mut self, bidding_window: Timestamp) -> Self {
+            self.bidding_window = Some(bidding_window);
+            self
+        }
+
+        /// Pays for the job with `amount` of the PSP22 `token` instead of the native balance.
+        /// The requester has to approve the catalog to spend `amount` before submitting it.
+        pub fn with_token(mut self, token: AccountId, amount: Balance) -> Self {
+            self.token = Some((token, amount));
+            self
+        }
+
+        /// Restricts the job to `workers`. Only they can accept or bid on it, and so be assigned,
+        /// commit a result and get paid for it.
+        pub fn with_allowed_workers(mut self, workers: Vec<AccountId>) -> Self {
+            self.allowed_workers = workers;
+            self
+        }
+
+        /// Hires `worker` directly, so that no one else can take the job.
+        pub fn with_worker(self, worker: AccountId) -> Self {
+            self.with_allowed_workers(Vec::from([worker]))
+        }
+
+        /// Releases the escrow in `milestones` equal parts. The worker reports progress after
+        /// each of the first `milestones - 1` parts of the work and the requester pays for them
+        /// as they come, while the last part is paid with the result. Only single-replica jobs
+        /// can be split into milestones.
+        pub fn with_milestones(mut self, milestones: u8) -> Self {
+            self.milestones = milestones;
+            self
+        }
+
+        pub fn timeout(&self) -> Timestamp {
+            self.timeout
+        }
+
+        pub fn replicas(&self) -> u8 {
+            self.replicas
+        }
+
+        pub fn requirements(&self) -> &JobRequirements {
+            &self.requirements
+        }
+
+        pub fn bidding_window(&self) -> Option<Timestamp> {
+            self.bidding_window
+        }
+
+        pub fn token(&self) -> Option<(AccountId, Balance)> {
+            self.token
+        }
+
+        /// Workers the job is restricted to, or an empty list if any worker can take it.
+        pub fn allowed_workers(&self) -> &[AccountId] {
+            &self.allowed_workers
+        }
+
+        pub fn milestones(&self) -> u8 {
+            self.milestones
+        }
+
+        /// Id of the job once `requester` submits it as their `nonce`th request.
+        pub fn id(&self, requester: AccountId, nonce: u64) -> HashId {
+            let data = (
+                self.code_hash,
+                self.params_hash,
+                &self.func_name,
+                requester,
+                nonce,
+            )
+                .encode();
+
+            hash(&data)
+        }
+    }
+
+    /// Storage of the catalog. Upgrades keep this layout, so fields must never be removed or
+    /// reordered; new state goes at the end as `Mapping` or `Lazy` fields.
+    #[ink(storage)]
+    pub struct Catalog {
+        workers: Workers,
+        stakes: Stakes,
+        jobs: Jobs,
+        job_index: JobIndex,
+        open_jobs: OpenJobs,
+        open_job_positions: OpenJobPositions,
+        open_job_count: u64,
+        worker_index: WorkerIndex,
+        worker_positions: WorkerPositions,
+        worker_count: u64,
+        nonces: Nonces,
+        commitments: Commitments,
+        reveals: Reveals,
+        disputes: Disputes,
+        reputations: Reputations,
+        arbiter: AccountId,
+        min_stake: Balance,
+        unbonding_period: Timestamp,
+        claim_window: Timestamp,
+        reveal_window: Timestamp,
+        dispute_bond: Balance,
+        dispute_quorum: u8,
+        owner: AccountId,
+        paused: bool,
+        treasury: Lazy<Treasury>,
+        auctions: Auctions,
+        sealed_bids: SealedBids,
+        bids: Bids,
+        job_tokens: JobTokens,
+        token_fees: TokenFees,
+        allowlists: Allowlists,
+        milestones: JobMilestones,
+        /// Number of unsettled jobs each worker holds a replica slot in.
+        active_jobs: ActiveJobs,
+    }
+
+    impl Default for Catalog {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Catalog {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::new_with_owner(Self::env().caller())
+        }
+
+        /// Instantiates the catalog with `owner` as both its owner and arbiter.
+        #[ink(constructor)]
+        pub fn new_with_owner(owner: AccountId) -> Self {
+            let mut treasury = Lazy::new();
+            treasury.set(&Treasury {
+                account: owner,
+                fee_bps: 0,
+                balance: 0,
+            });
+
+            Self {
+                workers: Mapping::new(),
+                stakes: Mapping::new(),
+                jobs: Mapping::new(),
+                job_index: Mapping::new(),
+                open_jobs: Mapping::new(),
+                open_job_positions: Mapping::new(),
+                open_job_count: 0,
+                worker_index: Mapping::new(),
+                worker_positions: Mapping::new(),
+                worker_count: 0,
+                nonces: Mapping::new(),
+                commitments: Mapping::new(),
+                reveals: Mapping::new(),
+                disputes: Mapping::new(),
+                reputations: Mapping::new(),
+                arbiter: owner,
+                min_stake: MIN_WORKER_STAKE,
+                unbonding_period: UNBONDING_PERIOD,
+                claim_window: CLAIM_WINDOW,
+                reveal_window: REVEAL_WINDOW,
+                dispute_bond: DISPUTE_BOND,
+                dispute_quorum: DISPUTE_QUORUM,
+                owner,
+                paused: false,
+                treasury,
+                auctions: Mapping::new(),
+                sealed_bids: Mapping::new(),
+                bids: Mapping::new(),
+                job_tokens: Mapping::new(),
+                token_fees: Mapping::new(),
+                allowlists: Mapping::new(),
+                milestones: Mapping::new(),
+                active_jobs: Mapping::new(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        #[ink(message)]
+        pub fn get_treasury(&self) -> Treasury {
+            self.treasury()
+        }
+
+        /// Sets the fee, in basis points, taken from every payout to workers.
+        #[ink(message)]
+        pub fn set_protocol_fee(&mut self, fee_bps: u16) -> Result<(), CatalogError> {
+            self.ensure_owner()?;
+
+            if fee_bps > MAX_PROTOCOL_FEE_BPS {
+                return Err(CatalogError::InvalidFee);
+            }
+
+            let mut treasury = self.treasury();
+            treasury.fee_bps = fee_bps;
+            self.treasury.set(&treasury);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_treasury(&mut self, account: AccountId) -> Result<(), CatalogError> {
+            self.ensure_owner()?;
+
+            let mut treasury = self.treasury();
+            treasury.account = account;
+            self.treasury.set(&treasury);
+
+            Ok(())
+        }
+
+        /// Sends the fees collected so far to the treasury account.
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut treasury = self.treasury();
+
+            if who != treasury.account {
+                return Err(CatalogError::NotTreasury);
+            }
+
+            let value = treasury.balance;
+            self.env()
+                .transfer(who, value)
+                .map_err(|_| CatalogError::FailedTransfer)?;
+
+            treasury.balance = 0;
+            self.treasury.set(&treasury);
+            self.env().emit_event(TreasuryWithdrawn {
+                who,
+                value,
+                token: None,
+            });
+
+            Ok(())
+        }
+
+        /// Fees collected so far on jobs paid with the PSP22 `token`.
+        #[ink(message)]
+        pub fn get_token_fees(&self, token: AccountId) -> Balance {
+            self.token_fees.get(token).unwrap_or(0)
+        }
+
+        /// Sends the fees collected so far in the PSP22 `token` to the treasury account.
+        #[ink(message)]
+        pub fn withdraw_token_fees(&mut self, token: AccountId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+
+            if who != self.treasury().account {
+                return Err(CatalogError::NotTreasury);
+            }
+
+            let value = self.get_token_fees(token);
+            self.psp22_transfer(token, who, value)?;
+
+            self.token_fees.remove(token);
+            self.env().emit_event(TreasuryWithdrawn {
+                who,
+                value,
+                token: Some(token),
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), CatalogError> {
+            let previous_owner = self.ensure_owner()?;
+
+            self.owner = new_owner;
+            self.env().emit_event(OwnershipTransferred {
+                previous_owner,
+                new_owner,
+            });
+
+            Ok(())
+        }
+
+        /// Stops new jobs from being submitted. Jobs already in escrow can still be worked on,
+        /// paid out, refunded and disputed.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), CatalogError> {
+            let who = self.ensure_owner()?;
+
+            self.paused = true;
+            self.env().emit_event(Paused { who });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), CatalogError> {
+            let who = self.ensure_owner()?;
+
+            self.paused = false;
+            self.env().emit_event(Unpaused { who });
+
+            Ok(())
+        }
+
+        /// Replaces the contract's code with the already uploaded `code_hash`, keeping its
+        /// storage and balance. The new code has to keep the storage layout of `Catalog`.
+        #[ink(message)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), CatalogError> {
+            self.ensure_owner()?;
+
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| CatalogError::UpgradeFailed)?;
+            self.env().emit_event(CodeUpgraded { code_hash });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_worker(&self, who: AccountId) -> Option<WorkerProfile> {
+            self.workers.get(who)
+        }
+
+        /// Registered workers, in no particular order.
+        #[ink(message)]
+        pub fn workers(&self, cursor: u64, limit: u32) -> Page<(AccountId, WorkerProfile)> {
+            paginate(cursor, limit, self.worker_count, |position| {
+                let who = self.worker_index.get(position)?;
+                self.workers.get(who).map(|profile| (who, profile))
+            })
+        }
+
+        #[ink(message)]
+        pub fn job(&self, job_id: HashId) -> Option<JobRecord> {
+            self.jobs.get(job_id)
+        }
+
+        /// Workers the job is restricted to, or an empty list if any worker can take it.
+        #[ink(message)]
+        pub fn job_allowlist(&self, job_id: HashId) -> Vec<AccountId> {
+            self.allowlists.get(job_id).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_milestones(&self, job_id: HashId) -> Option<Milestones> {
+            self.milestones.get(job_id)
+        }
+
+        /// PSP22 token the job is paid with, or `None` if it is paid with the native balance.
+        #[ink(message)]
+        pub fn job_token(&self, job_id: HashId) -> Option<AccountId> {
+            self.job_tokens.get(job_id)
+        }
+
+        /// Jobs submitted by `who`, oldest first.
+        #[ink(message)]
+        pub fn jobs_by_requester(
+            &self,
+            who: AccountId,
+            cursor: u64,
+            limit: u32,
+        ) -> Page<(HashId, JobRecord)> {
+            paginate(cursor, limit, self.get_job_count(who), |nonce| {
+                let id = self.job_index.get((who, nonce))?;
+                self.jobs.get(id).map(|record| (id, record))
+            })
+        }
+
+        /// Jobs still waiting for workers to accept them, in no particular order.
+        #[ink(message)]
+        pub fn open_jobs(&self, cursor: u64, limit: u32) -> Page<(HashId, JobRecord)> {
+            paginate(cursor, limit, self.open_job_count, |position| {
+                let id = self.open_jobs.get(position)?;
+                self.jobs.get(id).map(|record| (id, record))
+            })
+        }
+
+        /// Number of jobs `who` has submitted so far.
+        #[ink(message)]
+        pub fn get_job_count(&self, who: AccountId) -> u64 {
+            self.nonces.get(who).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_stake(&self, who: AccountId) -> Option<Stake> {
+            self.stakes.get(who)
+        }
+
+        #[ink(message)]
+        pub fn get_reputation(&self, who: AccountId) -> Reputation {
+            self.reputations.get(who).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        pub fn get_dispute(&self, job_id: HashId) -> Option<Dispute> {
+            self.disputes.get(job_id)
+        }
+
+        #[ink(message)]
+        pub fn get_auction(&self, job_id: HashId) -> Option<Auction> {
+            self.auctions.get(job_id)
+        }
+
+        #[ink(message)]
+        pub fn get_arbiter(&self) -> AccountId {
+            self.arbiter
+        }
+
+        /// Hands the arbiter role over to `arbiter`. Only the owner can do this.
+        #[ink(message)]
+        pub fn set_arbiter(&mut self, arbiter: AccountId) -> Result<(), CatalogError> {
+            self.ensure_owner()?;
+
+            self.arbiter = arbiter;
+
+            Ok(())
+        }
+
+        #[ink(message, payable)]
+        pub fn register_worker(&mut self, profile: WorkerProfile) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let mut stake = self.stakes.get(caller).unwrap_or(Stake {
+                amount: 0,
+                unbonding_at: None,
+            });
+
+            stake.amount = stake.amount.saturating_add(self.env().transferred_value());
+            stake.unbonding_at = None;
+
+            if stake.amount < self.min_stake {
+                return Err(CatalogError::InsufficientStake);
+            }
+
+            if !self.workers.contains(caller) {
+                self.index_worker(caller);
+            }
+
+            self.workers.insert(caller, &profile);
+            self.stakes.insert(caller, &stake);
+
+            self.env().emit_event(WorkerRegistered {
+                who: caller,
+                stake: stake.amount,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn update_worker_profile(
+            &mut self,
+            profile: WorkerProfile,
+        ) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+
+            if !self.workers.contains(caller) {
+                return Err(CatalogError::WorkerNotRegistered);
+            }
+
+            self.workers.insert(caller, &profile);
+            self.env().emit_event(WorkerProfileUpdated {
+                who: caller,
+                profile,
+            });
+
+            Ok(())
+        }
+
+        /// Starts unbonding the caller's stake. Fails while the caller still holds a slot in a
+        /// job that hasn't been paid or cancelled, as that stake backs its result.
+        #[ink(message)]
+        pub fn unregister_worker(&mut self) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let mut stake = self
+                .stakes
+                .get(caller)
+                .ok_or(CatalogError::WorkerNotStaked)?;
+
+            if stake.unbonding_at.is_some() {
+                return Err(CatalogError::WorkerUnbonding);
+            }
+
+            if self.active_jobs.get(caller).unwrap_or(0) > 0 {
+                return Err(CatalogError::WorkerHasOpenJobs);
+            }
+
+            let unbonding_at = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.unbonding_period);
+            stake.unbonding_at = Some(unbonding_at);
+
+            self.workers.remove(caller);
+            self.unindex_worker(caller);
+            self.stakes.insert(caller, &stake);
+            self.env().emit_event(WorkerUnbonding {
+                who: caller,
+                unbonding_at,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn withdraw_stake(&mut self) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let stake = self
+                .stakes
+                .get(caller)
+                .ok_or(CatalogError::WorkerNotStaked)?;
+
+            match stake.unbonding_at {
+                Some(unbonding_at) if self.env().block_timestamp() >= unbonding_at => {}
+                _ => return Err(CatalogError::UnbondingNotFinished),
+            }
+
+            self.env()
+                .transfer(caller, stake.amount)
+                .map_err(|_| CatalogError::FailedTransfer)?;
+            self.stakes.remove(caller);
+            self.env().emit_event(StakeWithdrawn {
+                who: caller,
+                value: stake.amount,
+            });
+
+            Ok(())
+        }
+
+        /// Slashes the workers that accepted `job_id` but never committed, or never revealed,
+        /// their result, and refunds the requester.
+        ///
+        /// The requester can only slash once the job's deadline has passed, while the arbiter
+        /// can do so at any time.
+        #[ink(message)]
+        pub fn slash_worker(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let mut record = self.job_record(job_id)?;
+
+            let revealing = match record.state {
+                JobState::Accepted => false,
+                JobState::Revealing => true,
+                _ => return Err(CatalogError::InvalidJobState),
+            };
+
+            if caller != self.arbiter {
+                if caller != record.requester {
+                    return Err(CatalogError::NotAuthorizedToSlash);
+                }
+
+                if self.env().block_timestamp() < record.deadline {
+                    return Err(CatalogError::DeadlineNotReached);
+                }
+            }
+
+            let faulty = record
+                .workers
+                .iter()
+                .filter(|worker| {
+                    let key = (job_id, **worker);
+
+                    if revealing {
+                        !self.reveals.contains(key)
+                    } else {
+                        !self.commitments.contains(key)
+                    }
+                })
+                .copied()
+                .collect::<Vec<AccountId>>();
+
+            let mut slashed: Balance = 0;
+            for worker in faulty {
+                slashed = slashed.saturating_add(self.slash(job_id, worker));
+                self.update_reputation(worker, |r| {
+                    r.jobs_abandoned = r.jobs_abandoned.saturating_add(1)
+                });
+            }
+
+            let token = self.job_tokens.get(job_id);
+            for (currency, amount) in payouts(token, record.value, slashed) {
+                self.transfer(currency, record.requester, amount)?;
+            }
+
+            self.release_workers(&record.workers);
+            record.state = JobState::Cancelled;
+            self.jobs.insert(job_id, &record);
+            self.env().emit_event(JobCancelled {
+                who: record.requester,
+                id: job_id,
+                value: record.value,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message, payable)]
+        pub fn submit_job_request(&mut self, job_request: JobRequest) -> Result<(), CatalogError> {
+            let value = self.env().transferred_value();
+
+            self.submit(job_request, value)
+        }
+
+        /// Submits several jobs at once, each escrowing its own amount out of the transferred
+        /// value. The amounts have to add up to the transferred value, and jobs paid with a
+        /// PSP22 token take an amount of zero.
+        #[ink(message, payable)]
+        pub fn submit_job_requests(
+            &mut self,
+            job_requests: Vec<(JobRequest, Balance)>,
+        ) -> Result<(), CatalogError> {
+            let total = job_requests
+                .iter()
+                .try_fold(0, |total: Balance, (_, amount)| total.checked_add(*amount))
+                .ok_or(CatalogError::InvalidPayment)?;
+
+            if total != self.env().transferred_value() {
+                return Err(CatalogError::InvalidPayment);
+            }
+
+            for (job_request, value) in job_requests {
+                self.submit(job_request, value)?;
+            }
+
+            Ok(())
+        }
+
+        fn submit(
+            &mut self,
+            job_request: JobRequest,
+            transferred_value: Balance,
+        ) -> Result<(), CatalogError> {
+            if self.paused {
+                return Err(CatalogError::ContractPaused);
+            }
+
+            let who = self.env().caller();
+            let nonce = self.nonces.get(who).unwrap_or(0);
+            let id = job_request.id(who, nonce);
+            let (token, value) = match job_request.token() {
+                Some((token, amount)) => (Some(token), amount),
+                None => (None, transferred_value),
+            };
+            let now = self.env().block_timestamp();
+            let deadline = now.saturating_add(job_request.timeout());
+
+            if self.jobs.contains(id) {
+                return Err(CatalogError::JobAlreadyExists);
+            }
+
+            if job_request.replicas() == 0 || job_request.replicas() > MAX_REPLICAS {
+                return Err(CatalogError::InvalidReplicas);
+            }
+
+            if token.is_some() && transferred_value > 0 {
+                return Err(CatalogError::InvalidPayment);
+            }
+
+            let allowed_workers = job_request.allowed_workers();
+            if allowed_workers.len() > usize::from(MAX_ALLOWED_WORKERS)
+                || (!allowed_workers.is_empty()
+                    && allowed_workers.len() < usize::from(job_request.replicas()))
+            {
+                return Err(CatalogError::InvalidAllowlist);
+            }
+
+            let milestones = job_request.milestones();
+            if milestones == 0
+                || milestones > MAX_MILESTONES
+                || (milestones > 1 && job_request.replicas() != 1)
+            {
+                return Err(CatalogError::InvalidMilestones);
+            }
+
+            let mut record = JobRecord {
+                requester: who,
+                value,
+                timeout: job_request.timeout(),
+                deadline,
+                replicas: job_request.replicas(),
+                requirements: job_request.requirements().clone(),
+                workers: Vec::new(),
+                state: JobState::Submitted,
+            };
+
+            let bidding_ends = match job_request.bidding_window() {
+                Some(bidding_window) => {
+                    if job_request.replicas() != 1 {
+                        return Err(CatalogError::InvalidReplicas);
+                    }
+
+                    let auction = Auction {
+                        bidding_ends: now.saturating_add(bidding_window),
+                        reveal_ends: now
+                            .saturating_add(bidding_window)
+                            .saturating_add(self.reveal_window),
+                        bidders: Vec::new(),
+                    };
+                    record.deadline = auction.reveal_ends;
+                    record.state = JobState::Bidding;
+                    self.auctions.insert(id, &auction);
+
+                    Some(auction.bidding_ends)
+                }
+                None => None,
+            };
+
+            if let Some(token) = token {
+                self.psp22_transfer_from(token, who, self.env().account_id(), value)?;
+                self.job_tokens.insert(id, &token);
+            }
+
+            if !job_request.allowed_workers().is_empty() {
+                self.allowlists.insert(id, &job_request.allowed_workers);
+            }
+
+            if milestones > 1 {
+                self.milestones.insert(
+                    id,
+                    &Milestones {
+                        count: milestones,
+                        amount: value / Balance::from(milestones),
+                        reported: 0,
+                        released: 0,
+                    },
+                );
+            }
+
+            self.jobs.insert(id, &record);
+            self.job_index.insert((who, nonce), &id);
+            self.open_job(id);
+            self.nonces.insert(who, &nonce.saturating_add(1));
+            self.env().emit_event(JobRequestSubmitted {
+                who,
+                id,
+                nonce,
+                value,
+                deadline: record.deadline,
+                timeout: job_request.timeout(),
+                replicas: job_request.replicas(),
+                requirements: job_request.requirements,
+                bidding_ends,
+                token,
+                allowed_workers: job_request.allowed_workers,
+                milestones,
+            });
+
+            Ok(())
+        }
+
+        /// Claims one of the job's replica slots for the caller. The job moves to `Accepted`,
+        /// and its deadline restarts, once every slot has been taken.
+        #[ink(message)]
+        pub fn accept_job_request(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut record = self.job_record(job_id)?;
+
+            match record.state {
+                JobState::Submitted => {}
+                JobState::Accepted => return Err(CatalogError::JobAlreadyAccepted),
+                _ => return Err(CatalogError::InvalidJobState),
+            }
+
+            let now = self.env().block_timestamp();
+            if now >= record.deadline {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            if record.workers.contains(&who) {
+                return Err(CatalogError::WorkerAlreadyAccepted);
+            }
+
+            self.ensure_eligible_worker(job_id, &record, who)?;
+
+            record.workers.push(who);
+            self.hold_workers(&[who]);
+            self.env().emit_event(JobAccepted { who, id: job_id });
+
+            if record.workers.len() == usize::from(record.replicas) {
+                record.deadline = now.saturating_add(record.timeout);
+                record.state = JobState::Accepted;
+                self.close_job(job_id);
+                self.env().emit_event(JobAssigned {
+                    id: job_id,
+                    workers: record.workers.clone(),
+                    deadline: record.deadline,
+                });
+            }
+
+            self.jobs.insert(job_id, &record);
+
+            Ok(())
+        }
+
+        /// Restricts a job nobody has been assigned to yet to `workers`, which the requester
+        /// picked among those that offered to run it. Workers outside the list that already
+        /// took a replica slot lose it.
+        #[ink(message)]
+        pub fn assign_workers(
+            &mut self,
+            job_id: HashId,
+            workers: Vec<AccountId>,
+        ) -> Result<(), CatalogError> {
+            let mut record = self.requester_job_record(job_id, self.env().caller())?;
+
+            if record.state != JobState::Submitted {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            if workers.len() > usize::from(MAX_ALLOWED_WORKERS)
+                || workers.len() < usize::from(record.replicas)
+            {
+                return Err(CatalogError::InvalidAllowlist);
+            }
+
+            let dropped = record
+                .workers
+                .iter()
+                .filter(|worker| !workers.contains(worker))
+                .copied()
+                .collect::<Vec<AccountId>>();
+            self.release_workers(&dropped);
+            record.workers.retain(|worker| workers.contains(worker));
+            self.jobs.insert(job_id, &record);
+            self.allowlists.insert(job_id, &workers);
+            self.env().emit_event(WorkersAssigned {
+                id: job_id,
+                workers,
+            });
+
+            Ok(())
+        }
+
+        /// Records the caller's sealed bid, `seal_bid(price, salt)`, on an auctioned job. Bids
+        /// stay hidden until the bidding window closes, so no worker can undercut another's.
+        #[ink(message)]
+        pub fn submit_bid(
+            &mut self,
+            job_id: HashId,
+            sealed_bid: HashId,
+        ) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let (record, mut auction) = self.auctioned_job(job_id)?;
+
+            if self.env().block_timestamp() >= auction.bidding_ends {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            if auction.bidders.contains(&who) {
+                return Err(CatalogError::BidAlreadySubmitted);
+            }
+
+            if auction.bidders.len() >= usize::from(MAX_BIDS) {
+                return Err(CatalogError::TooManyBids);
+            }
+
+            self.ensure_eligible_worker(job_id, &record, who)?;
+
+            auction.bidders.push(who);
+            self.auctions.insert(job_id, &auction);
+            self.sealed_bids.insert((job_id, who), &sealed_bid);
+            self.env().emit_event(BidSubmitted { who, id: job_id });
+
+            Ok(())
+        }
+
+        /// Opens the caller's sealed bid once the bidding window has closed. Bids above the
+        /// job's escrow are rejected.
+        #[ink(message)]
+        pub fn reveal_bid(
+            &mut self,
+            job_id: HashId,
+            price: Balance,
+            salt: HashId,
+        ) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let (record, auction) = self.auctioned_job(job_id)?;
+            let now = self.env().block_timestamp();
+
+            if now < auction.bidding_ends {
+                return Err(CatalogError::DeadlineNotReached);
+            }
+
+            if now >= auction.reveal_ends {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            let sealed_bid = self
+                .sealed_bids
+                .get((job_id, who))
+                .ok_or(CatalogError::InvalidBid)?;
+
+            if self.bids.contains((job_id, who)) {
+                return Err(CatalogError::BidAlreadySubmitted);
+            }
+
+            if seal_bid(price, salt) != sealed_bid || price > record.value {
+                return Err(CatalogError::InvalidBid);
+            }
+
+            self.bids.insert((job_id, who), &price);
+            self.env().emit_event(BidRevealed {
+                who,
+                id: job_id,
+                price,
+            });
+
+            Ok(())
+        }
+
+        /// Assigns an auctioned job to the worker with the lowest revealed bid, earliest bid
+        /// first on a tie, and refunds the requester whatever the escrow exceeds that bid by.
+        /// Without any revealed bid the job is cancelled instead. Anyone can close an auction
+        /// once its reveal window has passed.
+        #[ink(message)]
+        pub fn close_auction(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let (mut record, auction) = self.auctioned_job(job_id)?;
+            let now = self.env().block_timestamp();
+
+            if now < auction.reveal_ends {
+                return Err(CatalogError::DeadlineNotReached);
+            }
+
+            let winner = auction
+                .bidders
+                .iter()
+                .filter(|bidder| self.ensure_active_worker(**bidder).is_ok())
+                .filter_map(|bidder| {
+                    self.bids
+                        .get((job_id, *bidder))
+                        .map(|price| (*bidder, price))
+                })
+                .min_by_key(|(_, price)| *price);
+
+            for bidder in auction.bidders.iter() {
+                self.sealed_bids.remove((job_id, *bidder));
+                self.bids.remove((job_id, *bidder));
+            }
+            self.auctions.remove(job_id);
+            self.close_job(job_id);
+
+            let price = winner.map(|(_, price)| price).unwrap_or(0);
+            let refund = record.value.saturating_sub(price);
+            if refund > 0 {
+                self.transfer(self.job_tokens.get(job_id), record.requester, refund)?;
+            }
+
+            self.env().emit_event(AuctionClosed {
+                id: job_id,
+                winner: winner.map(|(worker, _)| worker),
+                price,
+            });
+
+            match winner {
+                Some((worker, price)) => {
+                    if let Some(mut milestones) = self.milestones.get(job_id) {
+                        milestones.amount = price / Balance::from(milestones.count);
+                        self.milestones.insert(job_id, &milestones);
+                    }
+
+                    record.value = price;
+                    record.workers.push(worker);
+                    self.hold_workers(&[worker]);
+                    record.deadline = now.saturating_add(record.timeout);
+                    record.state = JobState::Accepted;
+                    self.env().emit_event(JobAssigned {
+                        id: job_id,
+                        workers: record.workers.clone(),
+                        deadline: record.deadline,
+                    });
+                }
+                None => {
+                    record.state = JobState::Cancelled;
+                    self.env().emit_event(JobCancelled {
+                        who: record.requester,
+                        id: job_id,
+                        value: refund,
+                    });
+                }
+            }
+
+            self.jobs.insert(job_id, &record);
+
+            Ok(())
+        }
+
+        /// Records the caller's sealed result, `seal_result(result_hash, salt)`, so that no
+        /// replica can copy another's result before every replica has committed to its own.
+        /// Once all replicas have committed, the reveal window opens.
+        #[ink(message)]
+        pub fn commit_result(
+            &mut self,
+            job_id: HashId,
+            commitment: HashId,
+        ) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut record = self.job_record(job_id)?;
+            let now = self.env().block_timestamp();
+
+            if record.state != JobState::Accepted {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            if now >= record.deadline {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            if !record.workers.contains(&who) {
+                return Err(CatalogError::NotAssignedWorker);
+            }
+
+            if self.commitments.contains((job_id, who)) {
+                return Err(CatalogError::ResultAlreadyCommitted);
+            }
+
+            let accepted_at = record.deadline.saturating_sub(record.timeout);
+            self.update_reputation(who, |r| {
+                r.results_committed = r.results_committed.saturating_add(1);
+                r.total_time_to_result = r
+                    .total_time_to_result
+                    .saturating_add(now.saturating_sub(accepted_at));
+            });
+
+            self.commitments.insert((job_id, who), &commitment);
+            self.env().emit_event(JobResultCommitted {
+                who,
+                id: job_id,
+                commitment,
+                committed_at: now,
+            });
+
+            let committed = record
+                .workers
+                .iter()
+                .all(|worker| self.commitments.contains((job_id, *worker)));
+
+            if committed {
+                record.deadline = now.saturating_add(self.reveal_window);
+                record.state = JobState::Revealing;
+                self.jobs.insert(job_id, &record);
+                self.env().emit_event(RevealStarted {
+                    id: job_id,
+                    deadline: record.deadline,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Opens the caller's commitment. Once a quorum of replicas reveals the same hash, the
+        /// claim window opens in which the requester has to pay or dispute before the workers
+        /// can claim the escrow themselves. Replicas that disagree with the quorum are flagged
+        /// as outliers, and a job whose replicas cannot reach a quorum is disputed.
+        #[ink(message)]
+        pub fn reveal_result(
+            &mut self,
+            job_id: HashId,
+            result_hash: HashId,
+            salt: HashId,
+        ) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut record = self.job_record(job_id)?;
+            let now = self.env().block_timestamp();
+
+            match record.state {
+                JobState::Revealing | JobState::ResultCommitted { .. } => {}
+                _ => return Err(CatalogError::InvalidJobState),
+            }
+
+            if now >= record.deadline {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            let commitment = self
+                .commitments
+                .get((job_id, who))
+                .ok_or(CatalogError::NotAssignedWorker)?;
+
+            if self.reveals.contains((job_id, who)) {
+                return Err(CatalogError::ResultAlreadyRevealed);
+            }
+
+            if seal_result(result_hash, salt) != commitment {
+                return Err(CatalogError::InvalidReveal);
+            }
+
+            self.reveals.insert((job_id, who), &result_hash);
+            self.env().emit_event(JobResultRevealed {
+                who,
+                id: job_id,
+                result_hash,
+            });
+
+            if let JobState::ResultCommitted {
+                result_hash: agreed,
+            } = record.state
+            {
+                if agreed != result_hash {
+                    self.env().emit_event(ResultOutlier { who, id: job_id });
+                }
+                return Ok(());
+            }
+
+            let reveals = self.revealed_results(job_id, &record);
+            let agreeing = reveals
+                .iter()
+                .filter(|(_, hash)| *hash == result_hash)
+                .count();
+
+            if agreeing >= record.quorum() {
+                for (worker, hash) in reveals.iter() {
+                    if *hash != result_hash {
+                        self.env().emit_event(ResultOutlier {
+                            who: *worker,
+                            id: job_id,
+                        });
+                    }
+                }
+
+                record.deadline = now.saturating_add(self.claim_window);
+                record.state = JobState::ResultCommitted { result_hash };
+                self.env().emit_event(ResultAgreed {
+                    id: job_id,
+                    result_hash,
+                    deadline: record.deadline,
+                });
+            } else if reveals.len() == record.workers.len() {
+                record.state = JobState::Disputed;
+                self.open_dispute(job_id, 0);
+                self.env().emit_event(JobDisputed {
+                    who: record.requester,
+                    id: job_id,
+                });
+            }
+
+            self.jobs.insert(job_id, &record);
+
+            Ok(())
+        }
+
+        /// Disputes the job's result. The requester has to lock at least the dispute bond,
+        /// which is returned to them only if the dispute is resolved in their favour.
+        #[ink(message, payable)]
+        pub fn dispute_job(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut record = self.requester_job_record(job_id, who)?;
+
+            if !matches!(record.state, JobState::ResultCommitted { .. }) {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            if self.env().block_timestamp() >= record.deadline {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            let bond = self.env().transferred_value();
+            if bond < self.dispute_bond {
+                return Err(CatalogError::InsufficientBond);
+            }
+
+            record.state = JobState::Disputed;
+            self.jobs.insert(job_id, &record);
+            self.open_dispute(job_id, bond);
+            self.env().emit_event(JobDisputed { who, id: job_id });
+
+            Ok(())
+        }
+
+        /// Settles a disputed job with the arbiter's `verdict`.
+        #[ink(message)]
+        pub fn resolve_dispute(
+            &mut self,
+            job_id: HashId,
+            verdict: Option<HashId>,
+        ) -> Result<(), CatalogError> {
+            if self.env().caller() != self.arbiter {
+                return Err(CatalogError::NotArbiter);
+            }
+
+            let (record, dispute) = self.disputed_job(job_id)?;
+
+            self.resolve(job_id, record, dispute.bond, verdict)
+        }
+
+        /// Records the sealed ballot, `seal_result(result_hash, salt)`, of a staked worker that
+        /// re-executed the disputed job. Workers assigned to the job cannot vote, and ballots
+        /// stay hidden until voting closes so no voter can copy another's result.
+        #[ink(message)]
+        pub fn vote_dispute(&mut self, job_id: HashId, ballot: HashId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let (record, mut dispute) = self.disputed_job(job_id)?;
+
+            if who == record.requester || record.workers.contains(&who) {
+                return Err(CatalogError::NotEligibleToVote);
+            }
+
+            self.ensure_active_worker(who)?;
+
+            if self.env().block_timestamp() >= dispute.voting_ends {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            if dispute.ballots.iter().any(|(voter, _)| *voter == who) {
+                return Err(CatalogError::AlreadyVoted);
+            }
+
+            dispute.ballots.push((who, ballot));
+            self.disputes.insert(job_id, &dispute);
+            self.env().emit_event(DisputeBallotCast { who, id: job_id });
+
+            Ok(())
+        }
+
+        /// Opens the caller's ballot once voting has closed. The vote sides with the workers
+        /// that revealed `result_hash`, or with the requester if none of them did, and the
+        /// dispute is settled as soon as enough votes agree on the same verdict.
+        #[ink(message)]
+        pub fn reveal_vote(
+            &mut self,
+            job_id: HashId,
+            result_hash: HashId,
+            salt: HashId,
+        ) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let (record, mut dispute) = self.disputed_job(job_id)?;
+            let now = self.env().block_timestamp();
+
+            if now < dispute.voting_ends {
+                return Err(CatalogError::DeadlineNotReached);
+            }
+
+            if now >= dispute.reveal_ends {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            self.ensure_active_worker(who)?;
+
+            let (_, ballot) = dispute
+                .ballots
+                .iter()
+                .find(|(voter, _)| *voter == who)
+                .ok_or(CatalogError::NotEligibleToVote)?;
+
+            if seal_result(result_hash, salt) != *ballot {
+                return Err(CatalogError::InvalidReveal);
+            }
+
+            if dispute.votes.iter().any(|(voter, _)| *voter == who) {
+                return Err(CatalogError::AlreadyVoted);
+            }
+
+            let verdict = self
+                .revealed_results(job_id, &record)
+                .iter()
+                .any(|(_, hash)| *hash == result_hash)
+                .then_some(result_hash);
+
+            dispute.votes.push((who, verdict));
+            self.env().emit_event(DisputeVoted {
+                who,
+                id: job_id,
+                verdict,
+            });
+
+            let agreeing = dispute
+                .votes
+                .iter()
+                .filter(|(_, vote)| *vote == verdict)
+                .count();
+
+            if agreeing >= usize::from(self.dispute_quorum) {
+                return self.resolve(job_id, record, dispute.bond, verdict);
+            }
+
+            self.disputes.insert(job_id, &dispute);
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn cancel_job_request(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let mut record = self.requester_job_record(job_id, who)?;
+
+            match record.state {
+                JobState::Submitted => {}
+                JobState::Accepted => return Err(CatalogError::JobAlreadyAccepted),
+                _ => return Err(CatalogError::InvalidJobState),
+            }
+
+            if self.env().block_timestamp() < record.deadline {
+                return Err(CatalogError::DeadlineNotReached);
+            }
+
+            let value = record.value;
+            self.transfer(self.job_tokens.get(job_id), who, value)?;
+            self.release_workers(&record.workers);
+            record.state = JobState::Cancelled;
+            self.jobs.insert(job_id, &record);
+            self.close_job(job_id);
+            self.env().emit_event(JobCancelled {
+                who,
+                id: job_id,
+                value,
+            });
+
+            Ok(())
+        }
+
+        /// Splits the escrow between the workers whose results match the agreed hash.
+        #[ink(message)]
+        pub fn pay_worker(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let record = self.requester_job_record(job_id, caller)?;
+
+            let result_hash = match record.state {
+                JobState::ResultCommitted { result_hash } => result_hash,
+                _ => return Err(CatalogError::InvalidJobState),
+            };
+
+            self.settle(job_id, record, result_hash)
+        }
+
+        /// Records that the job's worker has finished the next part of the work, letting the
+        /// requester release that milestone's share of the escrow.
+        #[ink(message)]
+        pub fn report_progress(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let who = self.env().caller();
+            let record = self.job_record(job_id)?;
+
+            if record.state != JobState::Accepted {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            if self.env().block_timestamp() >= record.deadline {
+                return Err(CatalogError::DeadlineExpired);
+            }
+
+            if !record.workers.contains(&who) {
+                return Err(CatalogError::NotAssignedWorker);
+            }
+
+            let mut milestones = self
+                .milestones
+                .get(job_id)
+                .ok_or(CatalogError::InvalidMilestones)?;
+
+            if milestones.reported.saturating_add(1) >= milestones.count {
+                return Err(CatalogError::NoMilestoneLeft);
+            }
+
+            milestones.reported = milestones.reported.saturating_add(1);
+            self.milestones.insert(job_id, &milestones);
+            self.env().emit_event(ProgressReported {
+                who,
+                id: job_id,
+                milestone: milestones.reported,
+            });
+
+            Ok(())
+        }
+
+        /// Pays the worker for the next milestone it reported. Whatever has not been released
+        /// stays in escrow, and is refunded if the job is cancelled.
+        #[ink(message)]
+        pub fn pay_milestone(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let mut record = self.requester_job_record(job_id, caller)?;
+
+            match record.state {
+                JobState::Accepted | JobState::Revealing => {}
+                _ => return Err(CatalogError::InvalidJobState),
+            }
+
+            let mut milestones = self
+                .milestones
+                .get(job_id)
+                .ok_or(CatalogError::InvalidMilestones)?;
+
+            if milestones.released >= milestones.reported {
+                return Err(CatalogError::MilestoneNotReported);
+            }
+
+            let token = self.job_tokens.get(job_id);
+            let workers = record.workers.clone();
+            self.pay_out(job_id, record.requester, token, &workers, milestones.amount)?;
+
+            record.value = record.value.saturating_sub(milestones.amount);
+            milestones.released = milestones.released.saturating_add(1);
+            self.jobs.insert(job_id, &record);
+            self.milestones.insert(job_id, &milestones);
+            self.env().emit_event(MilestonePaid {
+                id: job_id,
+                milestone: milestones.released,
+                value: milestones.amount,
+            });
+
+            Ok(())
+        }
+
+        /// Lets a worker whose result matches the agreed hash settle the job once the requester
+        /// let the claim window pass without paying or disputing.
+        #[ink(message)]
+        pub fn claim_payment(&mut self, job_id: HashId) -> Result<(), CatalogError> {
+            let caller = self.env().caller();
+            let record = self.job_record(job_id)?;
+
+            let result_hash = match record.state {
+                JobState::ResultCommitted { result_hash } => result_hash,
+                _ => return Err(CatalogError::InvalidJobState),
+            };
+
+            if self.reveals.get((job_id, caller)) != Some(result_hash) {
+                return Err(CatalogError::NotAssignedWorker);
+            }
+
+            if self.env().block_timestamp() < record.deadline {
+                return Err(CatalogError::DeadlineNotReached);
+            }
+
+            self.settle(job_id, record, result_hash)
+        }
+
+        fn settle(
+            &mut self,
+            job_id: HashId,
+            mut record: JobRecord,
+            result_hash: HashId,
+        ) -> Result<(), CatalogError> {
+            let winners = self.agreeing_workers(job_id, &record, result_hash);
+            let token = self.job_tokens.get(job_id);
+
+            self.pay_out(job_id, record.requester, token, &winners, record.value)?;
+            self.complete_jobs(&winners);
+            self.release_workers(&record.workers);
+
+            record.state = JobState::Paid;
+            self.jobs.insert(job_id, &record);
+
+            Ok(())
+        }
+
+        /// Pays out a disputed job. If the verdict sides with the requester they get back the
+        /// escrow and their bond, otherwise both go to the workers that revealed the winning
+        /// hash. Every other worker assigned to the job is slashed, in favour of the winning
+        /// workers or, when the requester wins, of the treasury.
+        fn resolve(
+            &mut self,
+            job_id: HashId,
+            mut record: JobRecord,
+            bond: Balance,
+            verdict: Option<HashId>,
+        ) -> Result<(), CatalogError> {
+            let winners = match verdict {
+                Some(result_hash) => self.agreeing_workers(job_id, &record, result_hash),
+                None => Vec::new(),
+            };
+
+            let losers = record
+                .workers
+                .iter()
+                .filter(|worker| !winners.contains(worker))
+                .copied()
+                .collect::<Vec<AccountId>>();
+
+            let mut slashed: Balance = 0;
+            for worker in losers {
+                slashed = slashed.saturating_add(self.slash(job_id, worker));
+                self.update_reputation(worker, |r| {
+                    r.disputes_lost = r.disputes_lost.saturating_add(1)
+                });
+            }
+
+            let token = self.job_tokens.get(job_id);
+            if winners.is_empty() {
+                for (currency, amount) in payouts(token, record.value, bond) {
+                    self.transfer(currency, record.requester, amount)?;
+                }
+                let mut treasury = self.treasury();
+                treasury.balance = treasury.balance.saturating_add(slashed);
+                self.treasury.set(&treasury);
+                record.state = JobState::Cancelled;
+                self.env().emit_event(JobCancelled {
+                    who: record.requester,
+                    id: job_id,
+                    value: record.value.saturating_add(bond),
+                });
+            } else {
+                let penalties = bond.saturating_add(slashed);
+                for (currency, amount) in payouts(token, record.value, penalties) {
+                    self.pay_out(job_id, record.requester, currency, &winners, amount)?;
+                }
+                self.complete_jobs(&winners);
+                record.state = JobState::Paid;
+            }
+
+            self.release_workers(&record.workers);
+            self.jobs.insert(job_id, &record);
+            self.disputes.remove(job_id);
+            self.env().emit_event(DisputeResolved {
+                id: job_id,
+                verdict,
+            });
+
+            Ok(())
+        }
+
+        /// Splits `amount` of `token` equally between `winners`, less the protocol fee on each
+        /// share, and sends what cannot be split evenly back to the requester.
+        fn pay_out(
+            &mut self,
+            job_id: HashId,
+            requester: AccountId,
+            token: Option<AccountId>,
+            winners: &[AccountId],
+            amount: Balance,
+        ) -> Result<(), CatalogError> {
+            let share = amount / winners.len() as Balance;
+            let remainder = amount - share * winners.len() as Balance;
+            let mut treasury = self.treasury();
+            let fee = treasury.fee(share);
+
+            for worker in winners.iter().copied() {
+                self.transfer(token, worker, share - fee)?;
+                self.env().emit_event(PaidWorker {
+                    destination: worker,
+                    id: job_id,
+                    value: share - fee,
+                    fee,
+                    token,
+                });
+            }
+
+            let fees = fee * winners.len() as Balance;
+            match token {
+                Some(token) => {
+                    let collected = self.get_token_fees(token).saturating_add(fees);
+                    self.token_fees.insert(token, &collected);
+                }
+                None => {
+                    treasury.balance = treasury.balance.saturating_add(fees);
+                    self.treasury.set(&treasury);
+                }
+            }
+
+            if remainder > 0 {
+                self.transfer(token, requester, remainder)?;
+            }
+
+            Ok(())
+        }
+
+        fn hold_workers(&mut self, workers: &[AccountId]) {
+            for worker in workers.iter().copied() {
+                let active = self.active_jobs.get(worker).unwrap_or(0);
+                self.active_jobs.insert(worker, &active.saturating_add(1));
+            }
+        }
+
+        fn release_workers(&mut self, workers: &[AccountId]) {
+            for worker in workers.iter().copied() {
+                match self.active_jobs.get(worker).unwrap_or(0) {
+                    0 | 1 => self.active_jobs.remove(worker),
+                    active => {
+                        self.active_jobs.insert(worker, &(active - 1));
+                    }
+                }
+            }
+        }
+
+        fn complete_jobs(&mut self, workers: &[AccountId]) {
+            for worker in workers.iter().copied() {
+                self.update_reputation(worker, |r| {
+                    r.jobs_completed = r.jobs_completed.saturating_add(1)
+                });
+            }
+        }
+
+        /// Sends `value` to `to`, in the PSP22 `token` if one is given and in the native
+        /// balance otherwise.
+        fn transfer(
+            &self,
+            token: Option<AccountId>,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), CatalogError> {
+            match token {
+                Some(token) => self.psp22_transfer(token, to, value),
+                None => self
+                    .env()
+                    .transfer(to, value)
+                    .map_err(|_| CatalogError::FailedTransfer),
+            }
+        }
+
+        fn psp22_transfer(
+            &self,
+            token: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), CatalogError> {
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PSP22_TRANSFER_SELECTOR))
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| CatalogError::TokenTransferFailed)?
+                .map_err(|_| CatalogError::TokenTransferFailed)?
+                .map_err(|_| CatalogError::TokenTransferFailed)
+        }
+
+        /// Pulls `value` of the PSP22 `token` from `from`, which has to have approved the
+        /// catalog to spend at least that much.
+        fn psp22_transfer_from(
+            &self,
+            token: AccountId,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), CatalogError> {
+            build_call::<Environment>()
+                .call(token)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(PSP22_TRANSFER_FROM_SELECTOR))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(value)
+                        .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<Result<(), PSP22Error>>()
+                .try_invoke()
+                .map_err(|_| CatalogError::TokenTransferFailed)?
+                .map_err(|_| CatalogError::TokenTransferFailed)?
+                .map_err(|_| CatalogError::TokenTransferFailed)
+        }
+
+        /// Takes up to the minimum stake from `worker` and returns the slashed amount. A worker
+        /// that already withdrew its stake is slashed nothing, so the job can still be settled.
+        fn slash(&mut self, job_id: HashId, worker: AccountId) -> Balance {
+            let Some(mut stake) = self.stakes.get(worker) else {
+                return 0;
+            };
+            let slashed = stake.amount.min(self.min_stake);
+            stake.amount = stake.amount.saturating_sub(slashed);
+
+            self.stakes.insert(worker, &stake);
+            self.env().emit_event(WorkerSlashed {
+                who: worker,
+                id: job_id,
+                value: slashed,
+            });
+
+            slashed
+        }
+
+        fn open_job(&mut self, job_id: HashId) {
+            self.open_jobs.insert(self.open_job_count, &job_id);
+            self.open_job_positions.insert(job_id, &self.open_job_count);
+            self.open_job_count = self.open_job_count.saturating_add(1);
+        }
+
+        /// Drops `job_id` from the open jobs by moving the last open job into its position.
+        fn close_job(&mut self, job_id: HashId) {
+            let Some(position) = self.open_job_positions.take(job_id) else {
+                return;
+            };
+
+            self.open_job_count = self.open_job_count.saturating_sub(1);
+            let last = self.open_job_count;
+
+            if let Some(last_id) = self.open_jobs.take(last) {
+                if position != last {
+                    self.open_jobs.insert(position, &last_id);
+                    self.open_job_positions.insert(last_id, &position);
+                }
+            }
+        }
+
+        fn index_worker(&mut self, who: AccountId) {
+            self.worker_index.insert(self.worker_count, &who);
+            self.worker_positions.insert(who, &self.worker_count);
+            self.worker_count = self.worker_count.saturating_add(1);
+        }
+
+        /// Drops `who` from the worker index by moving the last worker into its position.
+        fn unindex_worker(&mut self, who: AccountId) {
+            let Some(position) = self.worker_positions.take(who) else {
+                return;
+            };
+
+            self.worker_count = self.worker_count.saturating_sub(1);
+            let last = self.worker_count;
+
+            if let Some(last_worker) = self.worker_index.take(last) {
+                if position != last {
+                    self.worker_index.insert(position, &last_worker);
+                    self.worker_positions.insert(last_worker, &position);
+                }
+            }
+        }
+
+        fn update_reputation(&mut self, who: AccountId, update: impl FnOnce(&mut Reputation)) {
+            let mut reputation = self.reputations.get(who).unwrap_or_default();
+            update(&mut reputation);
+            self.reputations.insert(who, &reputation);
+        }
+
+        fn agreeing_workers(
+            &self,
+            job_id: HashId,
+            record: &JobRecord,
+            result_hash: HashId,
+        ) -> Vec<AccountId> {
+            self.revealed_results(job_id, record)
+                .into_iter()
+                .filter(|(_, hash)| *hash == result_hash)
+                .map(|(worker, _)| worker)
+                .collect()
+        }
+
+        fn revealed_results(&self, job_id: HashId, record: &JobRecord) -> Vec<(AccountId, HashId)> {
+            record
+                .workers
+                .iter()
+                .filter_map(|worker| {
+                    self.reveals
+                        .get((job_id, *worker))
+                        .map(|hash| (*worker, hash))
+                })
+                .collect()
+        }
+
+        /// Checks that `who` may take the job: it has to be on the job's allowlist, if it has
+        /// one, be an active worker and meet the job's requirements.
+        fn ensure_eligible_worker(
+            &self,
+            job_id: HashId,
+            record: &JobRecord,
+            who: AccountId,
+        ) -> Result<(), CatalogError> {
+            if let Some(allowlist) = self.allowlists.get(job_id) {
+                if !allowlist.contains(&who) {
+                    return Err(CatalogError::NotAllowedWorker);
+                }
+            }
+
+            self.ensure_active_worker(who)?;
+
+            let profile = self
+                .workers
+                .get(who)
+                .ok_or(CatalogError::WorkerNotRegistered)?;
+            if !profile.meets(&record.requirements) {
+                return Err(CatalogError::RequirementsNotMet);
+            }
+
+            Ok(())
+        }
+
+        fn ensure_active_worker(&self, who: AccountId) -> Result<(), CatalogError> {
+            let stake = self.stakes.get(who).ok_or(CatalogError::WorkerNotStaked)?;
+
+            if stake.unbonding_at.is_some() {
+                return Err(CatalogError::WorkerUnbonding);
+            }
+
+            if stake.amount < self.min_stake {
+                return Err(CatalogError::InsufficientStake);
+            }
+
+            Ok(())
+        }
+
+        fn treasury(&self) -> Treasury {
+            self.treasury.get().unwrap_or(Treasury {
+                account: self.owner,
+                fee_bps: 0,
+                balance: 0,
+            })
+        }
+
+        fn ensure_owner(&self) -> Result<AccountId, CatalogError> {
+            let caller = self.env().caller();
+
+            if caller != self.owner {
+                return Err(CatalogError::NotOwner);
+            }
+
+            Ok(caller)
+        }
+
+        fn job_record(&self, job_id: HashId) -> Result<JobRecord, CatalogError> {
+            self.jobs.get(job_id).ok_or(CatalogError::JobNotFound)
+        }
+
+        fn auctioned_job(&self, job_id: HashId) -> Result<(JobRecord, Auction), CatalogError> {
+            let record = self.job_record(job_id)?;
+
+            if record.state != JobState::Bidding {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            let auction = self
+                .auctions
+                .get(job_id)
+                .ok_or(CatalogError::InvalidJobState)?;
+
+            Ok((record, auction))
+        }
+
+        /// Opens a dispute over the job, giving voters a reveal window to cast their ballots and
+        /// another one to reveal them.
+        fn open_dispute(&mut self, job_id: HashId, bond: Balance) {
+            let voting_ends = self
+                .env()
+                .block_timestamp()
+                .saturating_add(self.reveal_window);
+
+            self.disputes.insert(
+                job_id,
+                &Dispute {
+                    bond,
+                    voting_ends,
+                    reveal_ends: voting_ends.saturating_add(self.reveal_window),
+                    ballots: Vec::new(),
+                    votes: Vec::new(),
+                },
+            );
+        }
+
+        fn disputed_job(&self, job_id: HashId) -> Result<(JobRecord, Dispute), CatalogError> {
+            let record = self.job_record(job_id)?;
+
+            if record.state != JobState::Disputed {
+                return Err(CatalogError::InvalidJobState);
+            }
+
+            let dispute = self
+                .disputes
+                .get(job_id)
+                .ok_or(CatalogError::DisputeNotFound)?;
+
+            Ok((record, dispute))
+        }
+
+        fn requester_job_record(
+            &self,
+            job_id: HashId,
+            who: AccountId,
+        ) -> Result<JobRecord, CatalogError> {
+            let record = self.job_record(job_id)?;
+
+            if record.requester != who {
+                return Err(CatalogError::NotJobRequester);
+            }
+
+            Ok(record)
+        }
+    }
+
+    /// Hash a worker commits on-chain for the encoded results of a job.
+    pub fn result_hash(result: &[Vec<u8>]) -> HashId {
+        hash(&result.encode())
+    }
+
+    /// Commitment a worker submits for a result before revealing it with `salt`.
+    pub fn seal_result(result_hash: HashId, salt: HashId) -> HashId {
+        hash(&(result_hash, salt).encode())
+    }
+
+    /// Amounts a job pays out per currency: its escrow in the job's currency, and the bonds and
+    /// slashed stakes that come with it, which are always held in the native balance.
+    fn payouts(
+        token: Option<AccountId>,
+        escrow: Balance,
+        penalties: Balance,
+    ) -> Vec<(Option<AccountId>, Balance)> {
+        let mut payouts = Vec::new();
+
+        match token {
+            Some(_) => {
+                payouts.push((token, escrow));
+                if penalties > 0 {
+                    payouts.push((None, penalties));
+                }
+            }
+            None => payouts.push((None, escrow.saturating_add(penalties))),
+        }
+
+        payouts
+    }
+
+    /// Sealed bid a worker submits for an auctioned job before revealing `price` with `salt`.
+    pub fn seal_bid(price: Balance, salt: HashId) -> HashId {
+        hash(&(price, salt).encode())
+    }
+
+    /// Collects the items at positions `cursor..cursor + limit` of a list of `len` items,
+    /// skipping positions `item` has nothing for.
+    fn paginate<T>(cursor: u64, limit: u32, len: u64, item: impl Fn(u64) -> Option<T>) -> Page<T> {
+        let end = cursor
+            .saturating_add(u64::from(limit.min(MAX_PAGE_SIZE)))
+            .min(len);
+        let items = (cursor..end).filter_map(item).collect();
+        let next_cursor = (end < len).then_some(end);
+
+        Page { items, next_cursor }
+    }
+
+    fn hash(data: &[u8]) -> HashId {
+        let mut output = HashId::default();
+        hash_bytes::<Keccak256>(data, &mut output);
+        output
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::{
+            env::{
+                pay_with_call,
+                test::{
+                    get_account_balance, recorded_events, set_account_balance, set_block_timestamp,
+                    set_caller, EmittedEvent,
+                },
+                DefaultEnvironment,
+            },
+            primitives::AccountId,
+            scale::Decode,
+        };
+
+        impl JobRequest {
+            fn test(code: Vec<u8>) -> Self {
+                let params: Vec<Vec<u8>> = vec![];
+
+                Self::new(code.as_slice(), &params, b"add")
+            }
+        }
+
+        impl WorkerProfile {
+            fn test() -> Self {
+                Self::new(
+                    vec![ValueType::I32, ValueType::I64],
+                    1 << 20,
+                    1_000_000,
+                    1,
+                    vec![1, 2, 3],
+                    String::from("0.0.1"),
+                )
+            }
+        }
+
+        impl JobRecord {
+            fn test(requester: AccountId, value: Balance, deadline: Timestamp) -> Self {
+                Self {
+                    requester,
+                    value,
+                    timeout: DEFAULT_JOB_TIMEOUT,
+                    deadline,
+                    replicas: 1,
+                    requirements: JobRequirements::default(),
+                    workers: vec![],
+                    state: JobState::Submitted,
+                }
+            }
+
+            fn with_workers(mut self, workers: Vec<AccountId>) -> Self {
+                self.replicas = workers.len() as u8;
+                self.workers = workers;
+                self
+            }
+
+            fn with_state(mut self, state: JobState) -> Self {
+                self.state = state;
+                self
+            }
+        }
+
+        impl Dispute {
+            fn test(bond: Balance) -> Self {
+                Self {
+                    bond,
+                    voting_ends: REVEAL_WINDOW,
+                    reveal_ends: REVEAL_WINDOW * 2,
+                    ballots: vec![],
+                    votes: vec![],
+                }
+            }
+        }
+
+        impl Catalog {
+            fn stake_worker(&mut self, who: AccountId, amount: Balance) {
+                self.workers.insert(who, &WorkerProfile::test());
+                self.stakes.insert(
+                    who,
+                    &Stake {
+                        amount,
+                        unbonding_at: None,
+                    },
+                );
+            }
+
+            fn commit(&mut self, job_id: HashId, worker: AccountId, commitment: HashId) {
+                self.commitments.insert((job_id, worker), &commitment);
+            }
+
+            fn reveal(&mut self, job_id: HashId, worker: AccountId, result_hash: HashId) {
+                self.commitments
+                    .insert((job_id, worker), &seal_result(result_hash, [0; 32]));
+                self.reveals.insert((job_id, worker), &result_hash);
+            }
+        }
+
+        #[ink::test]
+        fn register_worker_emits_event() {
+            let who = AccountId::from([1; 32]);
+            let profile = WorkerProfile::test();
+            let mut catalog = Catalog::default();
+
+            pay_with_call!(catalog.register_worker(profile.clone()), MIN_WORKER_STAKE).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let worker_set_event =
+                <WorkerRegistered as Decode>::decode(&mut emitted_events[0].data.as_slice())
+                    .unwrap();
+            let stake = catalog.stakes.get(who).unwrap();
+
+            assert_eq!(emitted_events.len(), 1);
+            assert_eq!(worker_set_event.who, who);
+            assert_eq!(worker_set_event.stake, MIN_WORKER_STAKE);
+            assert_eq!(stake.amount, MIN_WORKER_STAKE);
+            assert_eq!(catalog.get_worker(who), Some(profile));
+        }
+
+        #[ink::test]
+        fn register_worker_fails_with_insufficient_stake() {
+            let mut catalog = Catalog::default();
+
+            assert_eq!(
+                pay_with_call!(
+                    catalog.register_worker(WorkerProfile::test()),
+                    MIN_WORKER_STAKE - 1
+                ),
+                Err(CatalogError::InsufficientStake)
+            );
+        }
+
+        #[ink::test]
+        fn update_worker_profile_emits_event_and_updates_storage() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+
+            pay_with_call!(
+                catalog.register_worker(WorkerProfile::test()),
+                MIN_WORKER_STAKE
+            )
+            .unwrap();
+
+            let mut profile = WorkerProfile::test();
+            profile.max_fuel = 10;
+            catalog.update_worker_profile(profile.clone()).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let updated_event =
+                <WorkerProfileUpdated as Decode>::decode(&mut emitted_events[1].data.as_slice())
+                    .unwrap();
+
+            assert_eq!(updated_event.who, who);
+            assert_eq!(updated_event.profile, profile);
+            assert_eq!(catalog.get_worker(who), Some(profile));
+        }
+
+        #[ink::test]
+        fn update_worker_profile_fails_for_unregistered_worker() {
+            let mut catalog = Catalog::default();
+
+            assert_eq!(
+                catalog.update_worker_profile(WorkerProfile::test()),
+                Err(CatalogError::WorkerNotRegistered)
+            );
+        }
+
+        #[ink::test]
+        fn unregister_worker_fails_until_open_jobs_are_settled() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT),
+            );
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.accept_job_request(job_id).unwrap();
+
+            assert_eq!(
+                catalog.unregister_worker(),
+                Err(CatalogError::WorkerHasOpenJobs)
+            );
+
+            let record = catalog
+                .jobs
+                .get(job_id)
+                .unwrap()
+                .with_state(JobState::ResultCommitted {
+                    result_hash: [7; 32],
+                });
+            catalog.jobs.insert(job_id, &record);
+            catalog.reveal(job_id, worker, [7; 32]);
+
+            set_caller::<DefaultEnvironment>(requester);
+            catalog.pay_worker(job_id).unwrap();
+
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.unregister_worker().unwrap();
+        }
+
+        #[ink::test]
+        fn withdraw_stake_succeeds_after_unbonding_period() {
+            let who = AccountId::from([4; 32]);
+            let mut catalog = Catalog::default();
+
+            set_account_balance::<DefaultEnvironment>(who, MIN_WORKER_STAKE * 2);
+            set_caller::<DefaultEnvironment>(who);
+
+            pay_with_call!(
+                catalog.register_worker(WorkerProfile::test()),
+                MIN_WORKER_STAKE
+            )
+            .unwrap();
+            catalog.unregister_worker().unwrap();
+
+            assert_eq!(
+                catalog.withdraw_stake(),
+                Err(CatalogError::UnbondingNotFinished)
+            );
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(who).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(UNBONDING_PERIOD);
+            catalog.withdraw_stake().unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(who).unwrap();
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let withdrawn_event =
+                <StakeWithdrawn as Decode>::decode(&mut emitted_events[2].data.as_slice()).unwrap();
+
+            assert_eq!(withdrawn_event.value, MIN_WORKER_STAKE);
+            assert_eq!(balance_after, balance_before + MIN_WORKER_STAKE);
+            assert!(catalog.stakes.get(who).is_none());
+            assert!(catalog.workers.get(who).is_none());
+        }
+
+        #[ink::test]
+        fn workers_pages_through_registered_workers() {
+            let workers = [2, 3, 4].map(|w| AccountId::from([w; 32]));
+            let mut catalog = Catalog::default();
+
+            for worker in workers {
+                set_account_balance::<DefaultEnvironment>(worker, MIN_WORKER_STAKE);
+                set_caller::<DefaultEnvironment>(worker);
+                pay_with_call!(
+                    catalog.register_worker(WorkerProfile::test()),
+                    MIN_WORKER_STAKE
+                )
+                .unwrap();
+            }
+
+            let first_page = catalog.workers(0, 2);
+            let second_page = catalog.workers(first_page.next_cursor().unwrap(), 2);
+
+            assert_eq!(first_page.items().len(), 2);
+            assert_eq!(first_page.items()[0].0, workers[0]);
+            assert_eq!(second_page.items().len(), 1);
+            assert_eq!(second_page.next_cursor(), None);
+
+            set_caller::<DefaultEnvironment>(workers[0]);
+            catalog.unregister_worker().unwrap();
+
+            let listed = catalog
+                .workers(0, MAX_PAGE_SIZE)
+                .into_items()
+                .into_iter()
+                .map(|(who, _)| who)
+                .collect::<Vec<AccountId>>();
+
+            assert_eq!(listed, vec![workers[2], workers[1]]);
+        }
+
+        #[ink::test]
+        fn open_jobs_only_lists_jobs_waiting_for_workers() {
+            let who = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+            let job_1_request = JobRequest::test(vec![1, 2, 3, 4]);
+            let job_2_request = JobRequest::test(vec![1, 2, 3, 5]);
+
+            pay_with_call!(catalog.submit_job_request(job_1_request.clone()), 100).unwrap();
+            pay_with_call!(catalog.submit_job_request(job_2_request.clone()), 100).unwrap();
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(worker);
+            catalog
+                .accept_job_request(job_1_request.id(who, 0))
+                .unwrap();
+
+            let open_jobs = catalog.open_jobs(0, MAX_PAGE_SIZE).into_items();
+            let requested_jobs = catalog.jobs_by_requester(who, 0, MAX_PAGE_SIZE);
+
+            assert_eq!(open_jobs.len(), 1);
+            assert_eq!(open_jobs[0].0, job_2_request.id(who, 1));
+            assert_eq!(requested_jobs.items().len(), 2);
+            assert_eq!(requested_jobs.items()[0].1.state, JobState::Accepted);
+        }
+
+        #[ink::test]
+        fn new_with_owner_sets_owner_and_arbiter() {
+            let owner = AccountId::from([2; 32]);
+            let catalog = Catalog::new_with_owner(owner);
+
+            assert_eq!(catalog.get_owner(), owner);
+            assert_eq!(catalog.get_arbiter(), owner);
+        }
+
+        #[ink::test]
+        fn set_arbiter_fails_for_non_owner() {
+            let owner = AccountId::from([1; 32]);
+            let arbiter = AccountId::from([2; 32]);
+            let mut catalog = Catalog::new_with_owner(owner);
+
+            set_caller::<DefaultEnvironment>(arbiter);
+            assert_eq!(catalog.set_arbiter(arbiter), Err(CatalogError::NotOwner));
+
+            set_caller::<DefaultEnvironment>(owner);
+            catalog.set_arbiter(arbiter).unwrap();
+            assert_eq!(catalog.get_arbiter(), arbiter);
+
+            set_caller::<DefaultEnvironment>(arbiter);
+            assert_eq!(catalog.set_arbiter(owner), Err(CatalogError::NotOwner));
+        }
+
+        #[ink::test]
+        fn transfer_ownership_fails_for_non_owner() {
+            let new_owner = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+
+            set_caller::<DefaultEnvironment>(new_owner);
+
+            assert_eq!(
+                catalog.transfer_ownership(new_owner),
+                Err(CatalogError::NotOwner)
+            );
+
+            set_caller::<DefaultEnvironment>(AccountId::from([1; 32]));
+            catalog.transfer_ownership(new_owner).unwrap();
+
+            assert_eq!(catalog.get_owner(), new_owner);
+            assert_eq!(catalog.pause(), Err(CatalogError::NotOwner));
+        }
+
+        #[ink::test]
+        fn pause_blocks_submissions_but_not_refunds() {
+            let requester = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog
+                .jobs
+                .insert(job_id, &JobRecord::test(requester, 100, 10));
+
+            catalog.pause().unwrap();
+
+            assert!(catalog.is_paused());
+            assert_eq!(
+                pay_with_call!(
+                    catalog.submit_job_request(JobRequest::test(vec![1, 2, 3, 5])),
+                    100
+                ),
+                Err(CatalogError::ContractPaused)
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            catalog.cancel_job_request(job_id).unwrap();
+            catalog.unpause().unwrap();
+
+            pay_with_call!(
+                catalog.submit_job_request(JobRequest::test(vec![1, 2, 3, 5])),
+                100
+            )
+            .unwrap();
+        }
+
+        #[ink::test]
+        fn submit_job_request_emits_event() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+            let code = vec![1, 2, 3, 4];
+            let value = 100;
+
+            let job_request = JobRequest::test(code.clone());
+
+            pay_with_call!(catalog.submit_job_request(job_request.clone()), value).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let job_submitted_event =
+                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[0].data.as_slice())
+                    .unwrap();
+            let record = catalog.jobs.get(job_request.id(who, 0)).unwrap();
+
+            assert_eq!(job_submitted_event.who, who);
+            assert_eq!(job_submitted_event.id, job_request.id(who, 0));
+            assert_eq!(job_submitted_event.nonce, 0);
+            assert_eq!(job_submitted_event.value, value);
+            assert_eq!(job_submitted_event.replicas, 1);
+            assert_eq!(emitted_events[0].topics.len(), 3);
+            assert_eq!(
+                catalog.open_jobs(0, 10).items()[0].0,
+                job_request.id(who, 0)
+            );
+            assert_eq!(record.requester, who);
+            assert_eq!(record.value, value);
+            assert_eq!(record.deadline, DEFAULT_JOB_TIMEOUT);
+            assert_eq!(record.state, JobState::Submitted);
+        }
+
+        #[ink::test]
+        fn submit_job_request_appends_to_existing_jobs() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let code_1 = vec![1, 2, 3, 4];
+            let code_2 = vec![1, 2, 3, 5];
+
+            let job_1_request = JobRequest::test(code_1.clone());
+            let job_2_request = JobRequest::test(code_2.clone());
+
+            pay_with_call!(catalog.submit_job_request(job_1_request.clone()), value).unwrap();
+            pay_with_call!(catalog.submit_job_request(job_2_request.clone()), value).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+
+            let job_submitted_event_1 =
+                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[0].data.as_slice())
+                    .unwrap();
+            let job_submitted_event_2 =
+                <JobRequestSubmitted as Decode>::decode(&mut emitted_events[1].data.as_slice())
+                    .unwrap();
+            assert_eq!(job_submitted_event_1.id, job_1_request.id(who, 0));
+            assert_eq!(job_submitted_event_2.id, job_2_request.id(who, 1));
+
+            let jobs = catalog.jobs_by_requester(who, 0, 10).into_items();
+
+            assert_eq!(catalog.get_job_count(who), 2);
+            assert_eq!(jobs.len(), 2);
+            assert_eq!(jobs[0].0, job_1_request.id(who, 0));
+            assert_eq!(jobs[1].0, job_2_request.id(who, 1));
+        }
+
+        #[ink::test]
+        fn submit_job_requests_escrows_each_job_and_emits_event_per_job() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+            let job_requests = vec![
+                (JobRequest::test(vec![1]), 30),
+                (JobRequest::test(vec![2]), 70),
+            ];
+
+            pay_with_call!(catalog.submit_job_requests(job_requests.clone()), 100).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let jobs = catalog.jobs_by_requester(who, 0, 10).into_items();
+
+            assert_eq!(emitted_events.len(), 2);
+            assert_eq!(jobs.len(), 2);
+
+            for (nonce, ((job_request, value), (id, record))) in
+                job_requests.iter().zip(jobs.iter()).enumerate()
+            {
+                let job_submitted_event = <JobRequestSubmitted as Decode>::decode(
+                    &mut emitted_events[nonce].data.as_slice(),
+                )
+                .unwrap();
+
+                assert_eq!(*id, job_request.id(who, nonce as u64));
+                assert_eq!(job_submitted_event.id, *id);
+                assert_eq!(job_submitted_event.value, *value);
+                assert_eq!(record.value, *value);
+            }
+        }
+
+        #[ink::test]
+        fn submit_job_requests_fails_when_amounts_do_not_match_value() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+            let job_requests = vec![
+                (JobRequest::test(vec![1]), 30),
+                (JobRequest::test(vec![2]), 70),
+            ];
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_requests(job_requests), 90),
+                Err(CatalogError::InvalidPayment)
+            );
+            assert_eq!(catalog.get_job_count(who), 0);
+        }
+
+        #[ink::test]
+        fn submit_job_request_fails_without_replicas() {
+            let mut catalog = Catalog::default();
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_replicas(0);
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_request(job_request), 100),
+                Err(CatalogError::InvalidReplicas)
+            );
+        }
+
+        #[ink::test]
+        fn submit_job_request_fails_when_paying_with_token_and_native_balance() {
+            let mut catalog = Catalog::default();
+            let job_request =
+                JobRequest::test(vec![1, 2, 3, 4]).with_token(AccountId::from([9; 32]), 100);
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_request(job_request), 100),
+                Err(CatalogError::InvalidPayment)
+            );
+        }
+
+        #[ink::test]
+        fn submit_job_request_assigns_new_id_to_resubmitted_job() {
+            let who = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]);
+
+            pay_with_call!(catalog.submit_job_request(job_request.clone()), 100).unwrap();
+            pay_with_call!(catalog.submit_job_request(job_request.clone()), 100).unwrap();
+
+            let jobs = catalog.jobs_by_requester(who, 0, 10).into_items();
+
+            assert_ne!(jobs[0].0, jobs[1].0);
+            assert_eq!(jobs[1].0, job_request.id(who, 1));
+            assert_eq!(catalog.nonces.get(who), Some(2));
+        }
+
+        #[ink::test]
+        fn job_request_id_covers_every_part_of_the_job() {
+            let requester = AccountId::from([1; 32]);
+            let code = vec![1, 2, 3, 4];
+            let params = vec![vec![1]];
+            let id = JobRequest::new(&code, &params, b"add").id(requester, 0);
+
+            assert_ne!(
+                id,
+                JobRequest::new(&code, &vec![vec![2]], b"add").id(requester, 0)
+            );
+            assert_ne!(id, JobRequest::new(&code, &params, b"sub").id(requester, 0));
+            assert_ne!(id, JobRequest::new(&code, &params, b"add").id(requester, 1));
+            assert_ne!(
+                id,
+                JobRequest::new(&code, &params, b"add").id(AccountId::from([2; 32]), 0)
+            );
+        }
+
+        #[ink::test]
+        fn pay_worker_emits_event_and_updates_storage() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]);
+            let value = 100;
+            let job_id = job_request.id(requester, 0);
+
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+            catalog.reveal(job_id, worker, [7; 32]);
+            catalog.pay_worker(job_id).unwrap();
+
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+
+            let paid_event =
+                <PaidWorker as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+
+            assert_eq!(paid_event.destination, worker);
+            assert_eq!(paid_event.id, job_id);
+            assert_eq!(paid_event.value, value);
+            assert_eq!(record.state, JobState::Paid);
+            assert_eq!(
+                catalog.pay_worker(job_id),
+                Err(CatalogError::InvalidJobState)
+            );
+        }
+
+        #[ink::test]
+        fn pay_worker_deducts_protocol_fee_into_treasury() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let treasury = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+            let value = 1_000;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+            catalog.reveal(job_id, worker, [7; 32]);
+            catalog.set_protocol_fee(250).unwrap();
+            catalog.set_treasury(treasury).unwrap();
+
+            let worker_balance_before = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+
+            catalog.pay_worker(job_id).unwrap();
+
+            let worker_balance_after = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let paid_event =
+                <PaidWorker as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+
+            assert_eq!(paid_event.value, 975);
+            assert_eq!(paid_event.fee, 25);
+            assert_eq!(worker_balance_after, worker_balance_before + 975);
+            assert_eq!(catalog.get_treasury().balance(), 25);
+
+            let treasury_balance_before =
+                get_account_balance::<DefaultEnvironment>(treasury).unwrap();
+
+            assert_eq!(catalog.withdraw_treasury(), Err(CatalogError::NotTreasury));
+
+            set_caller::<DefaultEnvironment>(treasury);
+            catalog.withdraw_treasury().unwrap();
+
+            let treasury_balance_after =
+                get_account_balance::<DefaultEnvironment>(treasury).unwrap();
+
+            assert_eq!(treasury_balance_after, treasury_balance_before + 25);
+            assert_eq!(catalog.get_treasury().balance(), 0);
+        }
+
+        #[ink::test]
+        fn set_protocol_fee_fails_above_maximum_or_for_non_owner() {
+            let mut catalog = Catalog::default();
+
+            assert_eq!(
+                catalog.set_protocol_fee(MAX_PROTOCOL_FEE_BPS + 1),
+                Err(CatalogError::InvalidFee)
+            );
+
+            set_caller::<DefaultEnvironment>(AccountId::from([2; 32]));
+
+            assert_eq!(catalog.set_protocol_fee(1), Err(CatalogError::NotOwner));
+        }
+
+        #[ink::test]
+        fn pay_worker_fails_for_other_requester() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+
+            assert_eq!(
+                catalog.pay_worker(job_id),
+                Err(CatalogError::NotJobRequester)
+            );
+        }
+
+        #[ink::test]
+        fn pay_worker_splits_escrow_between_matching_workers() {
+            let requester = AccountId::from([1; 32]);
+            let workers = vec![
+                AccountId::from([2; 32]),
+                AccountId::from([3; 32]),
+                AccountId::from([4; 32]),
+            ];
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 101, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(workers.clone())
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+            catalog.reveal(job_id, workers[0], [7; 32]);
+            catalog.reveal(job_id, workers[1], [8; 32]);
+            catalog.reveal(job_id, workers[2], [7; 32]);
+
+            catalog.pay_worker(job_id).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let paid_events = emitted_events
+                .iter()
+                .map(|ev| <PaidWorker as Decode>::decode(&mut ev.data.as_slice()).unwrap())
+                .collect::<Vec<PaidWorker>>();
+
+            assert_eq!(paid_events.len(), 2);
+            assert_eq!(paid_events[0].destination, workers[0]);
+            assert_eq!(paid_events[1].destination, workers[2]);
+            assert_eq!(paid_events[0].value, 50);
+            assert_eq!(paid_events[1].value, 50);
+            assert_eq!(catalog.get_reputation(workers[0]).jobs_completed(), 1);
+            assert_eq!(catalog.get_reputation(workers[1]).jobs_completed(), 0);
+        }
+
+        #[ink::test]
+        fn claim_payment_pays_worker_after_claim_window() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, CLAIM_WINDOW)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+            catalog.reveal(job_id, worker, [7; 32]);
+
+            set_caller::<DefaultEnvironment>(worker);
+
+            assert_eq!(
+                catalog.claim_payment(job_id),
+                Err(CatalogError::DeadlineNotReached)
+            );
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(CLAIM_WINDOW);
+            catalog.claim_payment(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(balance_after, balance_before + value);
+            assert_eq!(record.state, JobState::Paid);
+        }
+
+        #[ink::test]
+        fn claim_payment_fails_for_other_worker() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, 0)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+
+            set_caller::<DefaultEnvironment>(AccountId::from([4; 32]));
+
+            assert_eq!(
+                catalog.claim_payment(job_id),
+                Err(CatalogError::NotAssignedWorker)
+            );
+        }
+
+        #[ink::test]
+        fn accept_job_request_sets_worker_and_emits_event() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT),
+            );
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_block_timestamp::<DefaultEnvironment>(5);
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.accept_job_request(job_id).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let accepted_event =
+                <JobAccepted as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(accepted_event.who, worker);
+            assert_eq!(accepted_event.id, job_id);
+            assert_eq!(record.workers, vec![worker]);
+            assert_eq!(record.state, JobState::Accepted);
+            assert_eq!(record.deadline, 5 + DEFAULT_JOB_TIMEOUT);
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::JobAlreadyAccepted)
+            );
+        }
+
+        #[ink::test]
+        fn accept_job_request_waits_for_every_replica() {
+            let requester = AccountId::from([1; 32]);
+            let worker_1 = AccountId::from([3; 32]);
+            let worker_2 = AccountId::from([4; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            let mut record = JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT);
+            record.replicas = 2;
+            catalog.jobs.insert(job_id, &record);
+            catalog.stake_worker(worker_1, MIN_WORKER_STAKE);
+            catalog.stake_worker(worker_2, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(worker_1);
+            catalog.accept_job_request(job_id).unwrap();
+
+            assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Submitted);
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::WorkerAlreadyAccepted)
+            );
+
+            set_caller::<DefaultEnvironment>(worker_2);
+            catalog.accept_job_request(job_id).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let assigned_event =
+                <JobAssigned as Decode>::decode(&mut emitted_events[2].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(record.workers, vec![worker_1, worker_2]);
+            assert_eq!(record.state, JobState::Accepted);
+            assert_eq!(assigned_event.workers, record.workers);
+            assert_eq!(assigned_event.deadline, record.deadline);
+        }
+
+        #[ink::test]
+        fn accept_job_request_fails_when_requirements_not_met() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            let mut record = JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT);
+            record.requirements = JobRequirements::new(vec![ValueType::F64], 0, 0);
+            catalog.jobs.insert(job_id, &record);
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(worker);
+
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::RequirementsNotMet)
+            );
+        }
+
+        #[ink::test]
+        fn accept_job_request_fails_for_worker_not_on_allowlist() {
+            let requester = AccountId::from([1; 32]);
+            let allowed = AccountId::from([2; 32]);
+            let other = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_worker(allowed);
+            let job_id = job_request.id(requester, 0);
+
+            pay_with_call!(catalog.submit_job_request(job_request), 100).unwrap();
+            catalog.stake_worker(allowed, MIN_WORKER_STAKE);
+            catalog.stake_worker(other, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(other);
+
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::NotAllowedWorker)
+            );
+            assert_eq!(
+                catalog.commit_result(job_id, [1; 32]),
+                Err(CatalogError::InvalidJobState)
+            );
+
+            set_caller::<DefaultEnvironment>(allowed);
+            catalog.accept_job_request(job_id).unwrap();
+
+            assert_eq!(catalog.job_allowlist(job_id), vec![allowed]);
+            assert_eq!(catalog.jobs.get(job_id).unwrap().workers, vec![allowed]);
+        }
+
+        #[ink::test]
+        fn assign_workers_restricts_job_to_picked_workers() {
+            let requester = AccountId::from([1; 32]);
+            let early = AccountId::from([2; 32]);
+            let picked = vec![AccountId::from([3; 32]), AccountId::from([4; 32])];
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_replicas(2);
+            let job_id = job_request.id(requester, 0);
+
+            pay_with_call!(catalog.submit_job_request(job_request), 100).unwrap();
+            catalog.stake_worker(early, MIN_WORKER_STAKE);
+            catalog.stake_worker(picked[0], MIN_WORKER_STAKE);
+            catalog.stake_worker(picked[1], MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(early);
+            catalog.accept_job_request(job_id).unwrap();
+
+            assert_eq!(
+                catalog.assign_workers(job_id, picked.clone()),
+                Err(CatalogError::NotJobRequester)
+            );
+
+            set_caller::<DefaultEnvironment>(requester);
+            assert_eq!(
+                catalog.assign_workers(job_id, vec![picked[0]]),
+                Err(CatalogError::InvalidAllowlist)
+            );
+            catalog.assign_workers(job_id, picked.clone()).unwrap();
+
+            assert_eq!(catalog.job_allowlist(job_id), picked);
+            assert!(catalog.jobs.get(job_id).unwrap().workers.is_empty());
+
+            set_caller::<DefaultEnvironment>(early);
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::NotAllowedWorker)
+            );
+
+            set_caller::<DefaultEnvironment>(picked[0]);
+            catalog.accept_job_request(job_id).unwrap();
+            set_caller::<DefaultEnvironment>(picked[1]);
+            catalog.accept_job_request(job_id).unwrap();
+
+            let record = catalog.jobs.get(job_id).unwrap();
+            assert_eq!(record.workers, picked);
+            assert_eq!(record.state, JobState::Accepted);
+        }
+
+        #[ink::test]
+        fn submit_job_request_fails_when_allowlist_cannot_fill_replicas() {
+            let mut catalog = Catalog::default();
+            let job_request = JobRequest::test(vec![1, 2, 3, 4])
+                .with_worker(AccountId::from([2; 32]))
+                .with_replicas(2);
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_request(job_request), 100),
+                Err(CatalogError::InvalidAllowlist)
+            );
+        }
+
+        #[ink::test]
+        fn accept_job_request_fails_for_unstaked_worker() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT),
+            );
+
+            set_caller::<DefaultEnvironment>(worker);
+
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::WorkerNotStaked)
+            );
+        }
+
+        #[ink::test]
+        fn accept_job_request_fails_after_deadline() {
+            let requester = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog
+                .jobs
+                .insert(job_id, &JobRecord::test(requester, 100, 10));
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::DeadlineExpired)
+            );
+        }
+
+        #[ink::test]
+        fn commit_result_opens_reveal_window_once_every_replica_committed() {
+            let requester = AccountId::from([1; 32]);
+            let workers = vec![AccountId::from([2; 32]), AccountId::from([3; 32])];
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(workers.clone())
+                    .with_state(JobState::Accepted),
+            );
+
+            let commitment = seal_result(result_hash(&[vec![1, 2]]), [9; 32]);
+
+            set_block_timestamp::<DefaultEnvironment>(5);
+            set_caller::<DefaultEnvironment>(workers[0]);
+            catalog.commit_result(job_id, commitment).unwrap();
+
+            assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Accepted);
+            assert_eq!(
+                catalog.commit_result(job_id, commitment),
+                Err(CatalogError::ResultAlreadyCommitted)
+            );
+
+            set_caller::<DefaultEnvironment>(workers[1]);
+            catalog.commit_result(job_id, commitment).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let committed_event =
+                <JobResultCommitted as Decode>::decode(&mut emitted_events[0].data.as_slice())
+                    .unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(committed_event.who, workers[0]);
+            assert_eq!(committed_event.id, job_id);
+            assert_eq!(committed_event.commitment, commitment);
+            assert_eq!(record.state, JobState::Revealing);
+            assert_eq!(record.deadline, 5 + REVEAL_WINDOW);
+            assert_eq!(
+                catalog.get_reputation(workers[0]).average_time_to_result(),
+                Some(5)
+            );
+        }
+
+        #[ink::test]
+        fn reveal_result_moves_job_to_result_committed() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            let result_hash = result_hash(&[vec![1, 2]]);
+            let salt = [9; 32];
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Revealing),
+            );
+            catalog.commit(job_id, worker, seal_result(result_hash, salt));
+
+            set_block_timestamp::<DefaultEnvironment>(5);
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.reveal_result(job_id, result_hash, salt).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let revealed_event =
+                <JobResultRevealed as Decode>::decode(&mut emitted_events[0].data.as_slice())
+                    .unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(revealed_event.who, worker);
+            assert_eq!(revealed_event.result_hash, result_hash);
+            assert_eq!(record.state, JobState::ResultCommitted { result_hash });
+            assert_eq!(record.deadline, 5 + CLAIM_WINDOW);
+        }
+
+        #[ink::test]
+        fn reveal_result_fails_for_wrong_salt() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            let result_hash = result_hash(&[vec![1, 2]]);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Revealing),
+            );
+            catalog.commit(job_id, worker, seal_result(result_hash, [9; 32]));
+
+            set_caller::<DefaultEnvironment>(worker);
+
+            assert_eq!(
+                catalog.reveal_result(job_id, result_hash, [8; 32]),
+                Err(CatalogError::InvalidReveal)
+            );
+        }
+
+        #[ink::test]
+        fn reveal_result_settles_on_quorum_and_flags_outliers() {
+            let requester = AccountId::from([1; 32]);
+            let workers = vec![
+                AccountId::from([2; 32]),
+                AccountId::from([3; 32]),
+                AccountId::from([4; 32]),
+            ];
+            let hashes = [[7; 32], [8; 32], [7; 32]];
+            let salt = [9; 32];
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(workers.clone())
+                    .with_state(JobState::Revealing),
+            );
+            for (worker, hash) in workers.iter().zip(hashes) {
+                catalog.commit(job_id, *worker, seal_result(hash, salt));
+            }
+
+            set_caller::<DefaultEnvironment>(workers[0]);
+            catalog.reveal_result(job_id, hashes[0], salt).unwrap();
+            set_caller::<DefaultEnvironment>(workers[1]);
+            catalog.reveal_result(job_id, hashes[1], salt).unwrap();
+
+            assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Revealing);
+
+            set_caller::<DefaultEnvironment>(workers[2]);
+            catalog.reveal_result(job_id, hashes[2], salt).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let outlier_event =
+                <ResultOutlier as Decode>::decode(&mut emitted_events[3].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(outlier_event.who, workers[1]);
+            assert_eq!(
+                record.state,
+                JobState::ResultCommitted {
+                    result_hash: [7; 32]
+                }
+            );
+        }
+
+        #[ink::test]
+        fn reveal_result_disputes_job_without_quorum() {
+            let requester = AccountId::from([1; 32]);
+            let workers = vec![AccountId::from([2; 32]), AccountId::from([3; 32])];
+            let hashes = [[7; 32], [8; 32]];
+            let salt = [9; 32];
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(workers.clone())
+                    .with_state(JobState::Revealing),
+            );
+
+            for (worker, hash) in workers.iter().zip(hashes) {
+                catalog.commit(job_id, *worker, seal_result(hash, salt));
+                set_caller::<DefaultEnvironment>(*worker);
+                catalog.reveal_result(job_id, hash, salt).unwrap();
+            }
+
+            assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Disputed);
+            assert_eq!(catalog.get_dispute(job_id).unwrap().bond, 0);
+        }
+
+        #[ink::test]
+        fn commit_result_fails_for_other_worker() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+
+            set_caller::<DefaultEnvironment>(AccountId::from([4; 32]));
+
+            assert_eq!(
+                catalog.commit_result(job_id, [7; 32]),
+                Err(CatalogError::NotAssignedWorker)
+            );
+        }
+
+        #[ink::test]
+        fn dispute_job_moves_job_to_disputed() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+
+            pay_with_call!(catalog.dispute_job(job_id), DISPUTE_BOND).unwrap();
+
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let disputed_event =
+                <JobDisputed as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(disputed_event.who, requester);
+            assert_eq!(disputed_event.id, job_id);
+            assert_eq!(record.state, JobState::Disputed);
+            assert_eq!(catalog.get_dispute(job_id).unwrap().bond, DISPUTE_BOND);
+        }
+
+        #[ink::test]
+        fn dispute_job_fails_without_bond() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+
+            assert_eq!(
+                pay_with_call!(catalog.dispute_job(job_id), DISPUTE_BOND - 1),
+                Err(CatalogError::InsufficientBond)
+            );
+        }
+
+        #[ink::test]
+        fn dispute_job_fails_after_claim_window() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::ResultCommitted {
+                        result_hash: [7; 32],
+                    }),
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                catalog.dispute_job(job_id),
+                Err(CatalogError::DeadlineExpired)
+            );
+        }
+
+        #[ink::test]
+        fn dispute_job_fails_for_open_job() {
+            let requester = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT),
+            );
+
+            assert_eq!(
+                catalog.dispute_job(job_id),
+                Err(CatalogError::InvalidJobState)
+            );
+        }
+
+        #[ink::test]
+        fn dispute_job_fails_before_result_is_committed() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+
+            assert_eq!(
+                pay_with_call!(catalog.dispute_job(job_id), DISPUTE_BOND),
+                Err(CatalogError::InvalidJobState)
+            );
+        }
+
+        #[ink::test]
+        fn resolve_dispute_refunds_requester_and_slashes_workers() {
+            let requester = AccountId::from([2; 32]);
+            let workers = vec![AccountId::from([3; 32]), AccountId::from([4; 32])];
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(workers.clone())
+                    .with_state(JobState::Disputed),
+            );
+            catalog
+                .disputes
+                .insert(job_id, &Dispute::test(DISPUTE_BOND));
+            for worker in workers.iter() {
+                catalog.stake_worker(*worker, MIN_WORKER_STAKE);
+                catalog.reveal(job_id, *worker, [7; 32]);
+            }
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            catalog.resolve_dispute(job_id, None).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(balance_after, balance_before + value + DISPUTE_BOND);
+            assert_eq!(record.state, JobState::Cancelled);
+            assert_eq!(catalog.stakes.get(workers[0]).unwrap().amount, 0);
+            assert_eq!(catalog.get_treasury().balance(), MIN_WORKER_STAKE * 2);
+            assert!(catalog.get_dispute(job_id).is_none());
+        }
+
+        #[ink::test]
+        fn resolve_dispute_pays_workers_with_requester_bond() {
+            let requester = AccountId::from([1; 32]);
+            let winner = AccountId::from([3; 32]);
+            let loser = AccountId::from([4; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![winner, loser])
+                    .with_state(JobState::Disputed),
+            );
+            catalog
+                .disputes
+                .insert(job_id, &Dispute::test(DISPUTE_BOND));
+            catalog.stake_worker(winner, MIN_WORKER_STAKE);
+            catalog.stake_worker(loser, MIN_WORKER_STAKE);
+            catalog.reveal(job_id, winner, [7; 32]);
+            catalog.reveal(job_id, loser, [8; 32]);
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(winner).unwrap();
+
+            catalog.resolve_dispute(job_id, Some([7; 32])).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(winner).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(
+                balance_after,
+                balance_before + value + DISPUTE_BOND + MIN_WORKER_STAKE
+            );
+            assert_eq!(record.state, JobState::Paid);
+            assert_eq!(catalog.stakes.get(winner).unwrap().amount, MIN_WORKER_STAKE);
+            assert_eq!(catalog.stakes.get(loser).unwrap().amount, 0);
+            assert_eq!(catalog.get_reputation(winner).jobs_completed(), 1);
+            assert_eq!(catalog.get_reputation(loser).disputes_lost(), 1);
+        }
+
+        #[ink::test]
+        fn resolve_dispute_fails_for_non_arbiter() {
+            let requester = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_state(JobState::Disputed),
+            );
+
+            set_caller::<DefaultEnvironment>(requester);
+
+            assert_eq!(
+                catalog.resolve_dispute(job_id, None),
+                Err(CatalogError::NotArbiter)
+            );
+        }
+
+        #[ink::test]
+        fn vote_dispute_resolves_once_quorum_agrees() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let voters = [5, 6, 7].map(|v| AccountId::from([v; 32]));
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Disputed),
+            );
+            catalog
+                .disputes
+                .insert(job_id, &Dispute::test(DISPUTE_BOND));
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+            catalog.reveal(job_id, worker, [7; 32]);
+
+            for voter in voters.iter() {
+                catalog.stake_worker(*voter, MIN_WORKER_STAKE);
+                set_caller::<DefaultEnvironment>(*voter);
+                catalog
+                    .vote_dispute(job_id, seal_result([7; 32], [1; 32]))
+                    .unwrap();
+            }
+
+            set_block_timestamp::<DefaultEnvironment>(REVEAL_WINDOW);
+
+            for (i, voter) in voters.iter().enumerate() {
+                set_caller::<DefaultEnvironment>(*voter);
+
+                assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Disputed);
+                catalog.reveal_vote(job_id, [7; 32], [1; 32]).unwrap();
+
+                if i + 1 < usize::from(DISPUTE_QUORUM) {
+                    assert_eq!(catalog.get_dispute(job_id).unwrap().votes().len(), i + 1);
+                }
+            }
+
+            assert_eq!(catalog.jobs.get(job_id).unwrap().state, JobState::Paid);
+            assert!(catalog.get_dispute(job_id).is_none());
+        }
+
+        #[ink::test]
+        fn vote_dispute_fails_for_assigned_worker() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Disputed),
+            );
+            catalog.disputes.insert(job_id, &Dispute::test(0));
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(worker);
+
+            assert_eq!(
+                catalog.vote_dispute(job_id, seal_result([7; 32], [1; 32])),
+                Err(CatalogError::NotEligibleToVote)
+            );
+        }
+
+        #[ink::test]
+        fn reveal_vote_sides_with_requester_for_unrevealed_result() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let voter = AccountId::from([5; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, DEFAULT_JOB_TIMEOUT)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Disputed),
+            );
+            catalog
+                .disputes
+                .insert(job_id, &Dispute::test(DISPUTE_BOND));
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+            catalog.stake_worker(voter, MIN_WORKER_STAKE);
+            catalog.reveal(job_id, worker, [7; 32]);
+
+            set_caller::<DefaultEnvironment>(voter);
+            catalog
+                .vote_dispute(job_id, seal_result([8; 32], [1; 32]))
+                .unwrap();
+
+            assert_eq!(
+                catalog.reveal_vote(job_id, [8; 32], [1; 32]),
+                Err(CatalogError::DeadlineNotReached)
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(REVEAL_WINDOW);
+
+            assert_eq!(
+                catalog.reveal_vote(job_id, [7; 32], [1; 32]),
+                Err(CatalogError::InvalidReveal)
+            );
+
+            catalog.reveal_vote(job_id, [8; 32], [1; 32]).unwrap();
+
+            assert_eq!(
+                catalog.get_dispute(job_id).unwrap().votes(),
+                &[(voter, None)]
+            );
+        }
+
+        #[ink::test]
+        fn cancel_job_request_refunds_requester_after_deadline() {
+            let requester = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog
+                .jobs
+                .insert(job_id, &JobRecord::test(requester, value, 10));
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller::<DefaultEnvironment>(requester);
+            catalog.cancel_job_request(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let cancelled_event =
+                <JobCancelled as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(cancelled_event.who, requester);
+            assert_eq!(cancelled_event.id, job_id);
+            assert_eq!(cancelled_event.value, value);
+            assert_eq!(balance_after, balance_before + value);
+            assert_eq!(record.state, JobState::Cancelled);
+            assert_eq!(
+                catalog.cancel_job_request(job_id),
+                Err(CatalogError::InvalidJobState)
+            );
+        }
+
+        #[ink::test]
+        fn cancel_job_request_fails_before_deadline() {
+            let requester = AccountId::from([1; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog
+                .jobs
+                .insert(job_id, &JobRecord::test(requester, 100, 10));
+
+            assert_eq!(
+                catalog.cancel_job_request(job_id),
+                Err(CatalogError::DeadlineNotReached)
+            );
+        }
+
+        #[ink::test]
+        fn cancel_job_request_fails_once_accepted() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                catalog.cancel_job_request(job_id),
+                Err(CatalogError::JobAlreadyAccepted)
+            );
+        }
+
+        #[ink::test]
+        fn close_auction_assigns_job_to_lowest_bid_and_refunds_rest() {
+            let requester = AccountId::from([3; 32]);
+            let bids = [
+                (AccountId::from([2; 32]), 60),
+                (AccountId::from([4; 32]), 40),
+            ];
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_auction(10);
+            let job_id = job_request.id(requester, 0);
+
+            set_caller::<DefaultEnvironment>(requester);
+            pay_with_call!(catalog.submit_job_request(job_request), value).unwrap();
+
+            for (bidder, price) in bids {
+                catalog.stake_worker(bidder, MIN_WORKER_STAKE);
+                set_caller::<DefaultEnvironment>(bidder);
+                catalog
+                    .submit_bid(job_id, seal_bid(price, [price as u8; 32]))
+                    .unwrap();
+            }
+
+            assert_eq!(
+                catalog.accept_job_request(job_id),
+                Err(CatalogError::InvalidJobState)
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            for (bidder, price) in bids {
+                set_caller::<DefaultEnvironment>(bidder);
+                catalog
+                    .reveal_bid(job_id, price, [price as u8; 32])
+                    .unwrap();
+            }
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(10 + REVEAL_WINDOW);
+            catalog.close_auction(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let closed_event =
+                <AuctionClosed as Decode>::decode(&mut emitted_events[5].data.as_slice()).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(closed_event.winner, Some(bids[1].0));
+            assert_eq!(closed_event.price, 40);
+            assert_eq!(balance_after, balance_before + 60);
+            assert_eq!(record.workers, vec![bids[1].0]);
+            assert_eq!(record.value, 40);
+            assert_eq!(record.state, JobState::Accepted);
+            assert_eq!(record.deadline, 10 + REVEAL_WINDOW + DEFAULT_JOB_TIMEOUT);
+            assert_eq!(catalog.get_auction(job_id), None);
+            assert!(catalog.open_jobs(0, 10).items().is_empty());
+        }
+
+        #[ink::test]
+        fn close_auction_cancels_job_without_revealed_bids() {
+            let requester = AccountId::from([3; 32]);
+            let bidder = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_auction(10);
+            let job_id = job_request.id(requester, 0);
+
+            set_caller::<DefaultEnvironment>(requester);
+            pay_with_call!(catalog.submit_job_request(job_request), value).unwrap();
+
+            catalog.stake_worker(bidder, MIN_WORKER_STAKE);
+            set_caller::<DefaultEnvironment>(bidder);
+            catalog.submit_bid(job_id, seal_bid(50, [0; 32])).unwrap();
+
+            assert_eq!(
+                catalog.close_auction(job_id),
+                Err(CatalogError::DeadlineNotReached)
+            );
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(10 + REVEAL_WINDOW);
+            catalog.close_auction(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(balance_after, balance_before + value);
+            assert_eq!(record.state, JobState::Cancelled);
+            assert!(record.workers.is_empty());
+        }
+
+        #[ink::test]
+        fn submit_bid_fails_after_bidding_window() {
+            let requester = AccountId::from([1; 32]);
+            let bidder = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_auction(10);
+            let job_id = job_request.id(requester, 0);
+
+            pay_with_call!(catalog.submit_job_request(job_request), 100).unwrap();
+            catalog.stake_worker(bidder, MIN_WORKER_STAKE);
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller::<DefaultEnvironment>(bidder);
+
+            assert_eq!(
+                catalog.submit_bid(job_id, seal_bid(50, [0; 32])),
+                Err(CatalogError::DeadlineExpired)
+            );
+        }
+
+        #[ink::test]
+        fn reveal_bid_fails_for_wrong_salt_or_price_above_escrow() {
+            let requester = AccountId::from([1; 32]);
+            let bidder = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_auction(10);
+            let job_id = job_request.id(requester, 0);
+
+            pay_with_call!(catalog.submit_job_request(job_request), 100).unwrap();
+            catalog.stake_worker(bidder, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(bidder);
+            catalog.submit_bid(job_id, seal_bid(150, [0; 32])).unwrap();
+
+            assert_eq!(
+                catalog.reveal_bid(job_id, 150, [0; 32]),
+                Err(CatalogError::DeadlineNotReached)
+            );
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+
+            assert_eq!(
+                catalog.reveal_bid(job_id, 150, [1; 32]),
+                Err(CatalogError::InvalidBid)
+            );
+            assert_eq!(
+                catalog.reveal_bid(job_id, 150, [0; 32]),
+                Err(CatalogError::InvalidBid)
+            );
+        }
+
+        #[ink::test]
+        fn submit_job_request_fails_for_auction_with_replicas() {
+            let mut catalog = Catalog::default();
+            let job_request = JobRequest::test(vec![1, 2, 3, 4])
+                .with_auction(10)
+                .with_replicas(2);
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_request(job_request), 100),
+                Err(CatalogError::InvalidReplicas)
+            );
+        }
+
+        #[ink::test]
+        fn pay_milestone_releases_reported_milestones_and_refunds_the_rest() {
+            let requester = AccountId::from([3; 32]);
+            let worker = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_milestones(4);
+            let job_id = job_request.id(requester, 0);
+
+            set_caller::<DefaultEnvironment>(requester);
+            pay_with_call!(catalog.submit_job_request(job_request), value).unwrap();
+
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.accept_job_request(job_id).unwrap();
+            catalog.report_progress(job_id).unwrap();
+
+            let worker_balance_before = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+
+            set_caller::<DefaultEnvironment>(requester);
+            catalog.pay_milestone(job_id).unwrap();
+
+            let worker_balance_after = get_account_balance::<DefaultEnvironment>(worker).unwrap();
+            let milestones = catalog.get_milestones(job_id).unwrap();
+
+            assert_eq!(worker_balance_after, worker_balance_before + 25);
+            assert_eq!(milestones.released(), 1);
+            assert_eq!(catalog.jobs.get(job_id).unwrap().value, 75);
+            assert_eq!(
+                catalog.pay_milestone(job_id),
+                Err(CatalogError::MilestoneNotReported)
+            );
+
+            let requester_balance_before =
+                get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(DEFAULT_JOB_TIMEOUT);
+            catalog.slash_worker(job_id).unwrap();
+
+            let requester_balance_after =
+                get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            assert_eq!(
+                requester_balance_after,
+                requester_balance_before + 75 + MIN_WORKER_STAKE
+            );
+        }
+
+        #[ink::test]
+        fn report_progress_fails_past_last_milestone() {
+            let requester = AccountId::from([1; 32]);
+            let worker = AccountId::from([2; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_request = JobRequest::test(vec![1, 2, 3, 4]).with_milestones(2);
+            let job_id = job_request.id(requester, 0);
+
+            pay_with_call!(catalog.submit_job_request(job_request), 100).unwrap();
+
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+            set_caller::<DefaultEnvironment>(worker);
+            catalog.accept_job_request(job_id).unwrap();
+            catalog.report_progress(job_id).unwrap();
+
+            assert_eq!(
+                catalog.report_progress(job_id),
+                Err(CatalogError::NoMilestoneLeft)
+            );
+        }
+
+        #[ink::test]
+        fn submit_job_request_fails_for_milestones_with_replicas() {
+            let mut catalog = Catalog::default();
+            let job_request = JobRequest::test(vec![1, 2, 3, 4])
+                .with_milestones(2)
+                .with_replicas(2);
+
+            assert_eq!(
+                pay_with_call!(catalog.submit_job_request(job_request), 100),
+                Err(CatalogError::InvalidMilestones)
+            );
+        }
+
+        #[ink::test]
+        fn slash_worker_refunds_requester_after_deadline() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+            catalog.stake_worker(worker, MIN_WORKER_STAKE * 2);
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller::<DefaultEnvironment>(requester);
+            catalog.slash_worker(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let emitted_events = recorded_events().collect::<Vec<EmittedEvent>>();
+            let slashed_event =
+                <WorkerSlashed as Decode>::decode(&mut emitted_events[0].data.as_slice()).unwrap();
+            let stake = catalog.stakes.get(worker).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(slashed_event.who, worker);
+            assert_eq!(slashed_event.value, MIN_WORKER_STAKE);
+            assert_eq!(stake.amount, MIN_WORKER_STAKE);
+            assert_eq!(balance_after, balance_before + value + MIN_WORKER_STAKE);
+            assert_eq!(record.state, JobState::Cancelled);
+            assert_eq!(catalog.get_reputation(worker).jobs_abandoned(), 1);
+        }
+
+        #[ink::test]
+        fn slash_worker_refunds_requester_after_worker_withdrew_stake() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+            let value = 100;
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, value, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+
+            let balance_before = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller::<DefaultEnvironment>(requester);
+            catalog.slash_worker(job_id).unwrap();
+
+            let balance_after = get_account_balance::<DefaultEnvironment>(requester).unwrap();
+            let record = catalog.jobs.get(job_id).unwrap();
+
+            assert_eq!(balance_after, balance_before + value);
+            assert_eq!(record.state, JobState::Cancelled);
+            assert_eq!(catalog.get_reputation(worker).jobs_abandoned(), 1);
+        }
+
+        #[ink::test]
+        fn slash_worker_fails_before_deadline() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_caller::<DefaultEnvironment>(requester);
+
+            assert_eq!(
+                catalog.slash_worker(job_id),
+                Err(CatalogError::DeadlineNotReached)
+            );
+        }
+
+        #[ink::test]
+        fn slash_worker_fails_for_unauthorized_caller() {
+            let requester = AccountId::from([2; 32]);
+            let worker = AccountId::from([3; 32]);
+            let mut catalog = Catalog::default();
+
+            let job_id = JobRequest::test(vec![1, 2, 3, 4]).id(requester, 0);
+            catalog.jobs.insert(
+                job_id,
+                &JobRecord::test(requester, 100, 10)
+                    .with_workers(vec![worker])
+                    .with_state(JobState::Accepted),
+            );
+            catalog.stake_worker(worker, MIN_WORKER_STAKE);
+
+            set_block_timestamp::<DefaultEnvironment>(10);
+            set_caller::<DefaultEnvironment>(AccountId::from([4; 32]));
+
+            assert_eq!(
+                catalog.slash_worker(job_id),
+                Err(CatalogError::NotAuthorizedToSlash)
+            );
         }
     }
 }
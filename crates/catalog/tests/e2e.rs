@@ -1,11 +1,15 @@
 #[cfg(test)]
 mod tests {
 
-    use catalog::catalog::{Catalog, CatalogRef, JobRequest, JobRequestSubmitted, PaidWorker};
+    use catalog::catalog::{
+        result_hash, seal_result, Catalog, CatalogRef, JobCancelled, JobRequest,
+        JobRequestSubmitted, PaidWorker, ValueType, WorkerProfile, MIN_WORKER_STAKE,
+    };
 
     use codec::Decode;
     use ink::env::DefaultEnvironment;
     use ink_e2e::{alice, bob, events::ContractEmitted, ChainBackend, ContractsBackend};
+    use psp22_token::psp22_token::{Token, TokenRef};
 
     type E2EResult<T> = Result<T, Box<dyn std::error::Error>>;
 
@@ -23,7 +27,7 @@ mod tests {
         let params = vec![vec![1, 2, 3, 4]];
         let value = 100;
 
-        let job_request = JobRequest::new(&code, &params);
+        let job_request = JobRequest::new(&code, &params, b"add");
 
         let mut call_builder = contract.call_builder::<Catalog>();
 
@@ -57,9 +61,8 @@ mod tests {
     async fn pay_worker_emits_event<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
         let mut constructor = CatalogRef::new();
         let alice = alice();
-        let worker = bob().public_key().to_account_id();
-
-        let worker_balance_before = client.free_balance(worker.0.into()).await.unwrap();
+        let bob = bob();
+        let worker = bob.public_key().to_account_id();
 
         let contract = client
             .instantiate("catalog", &alice, &mut constructor)
@@ -71,8 +74,8 @@ mod tests {
         let params = vec![vec![1, 2, 3, 4]];
         let value = 100;
 
-        let job_request = JobRequest::new(&code, &params);
-        let job_id = job_request.id();
+        let job_request = JobRequest::new(&code, &params, b"add");
+        let job_id = job_request.id(alice.public_key().0.into(), 0);
 
         let mut call_builder = contract.call_builder::<Catalog>();
 
@@ -84,7 +87,44 @@ mod tests {
             .await
             .unwrap();
 
-        let pay_worker = call_builder.pay_worker(worker.0.into(), job_id);
+        let profile = WorkerProfile::new(
+            vec![ValueType::I32, ValueType::I64],
+            1 << 20,
+            1_000_000,
+            1,
+            vec![],
+            String::from("0.0.1"),
+        );
+        let register_worker = call_builder.register_worker(profile);
+        let _ = client
+            .call(&bob, &register_worker)
+            .value(MIN_WORKER_STAKE)
+            .submit()
+            .await
+            .unwrap();
+
+        let accept_job = call_builder.accept_job_request(job_id);
+        let _ = client.call(&bob, &accept_job).submit().await.unwrap();
+
+        let result_hash = result_hash(&[vec![24]]);
+        let salt = [9; 32];
+
+        let commit_result = call_builder.commit_result(job_id, seal_result(result_hash, salt));
+        let _ = client.call(&bob, &commit_result).submit().await.unwrap();
+
+        let reveal_result = call_builder.reveal_result(job_id, result_hash, salt);
+        let _ = client.call(&bob, &reveal_result).submit().await.unwrap();
+
+        let set_protocol_fee = call_builder.set_protocol_fee(500);
+        let _ = client
+            .call(&alice, &set_protocol_fee)
+            .submit()
+            .await
+            .unwrap();
+
+        let worker_balance_before = client.free_balance(worker.0.into()).await.unwrap();
+
+        let pay_worker = call_builder.pay_worker(job_id);
 
         let response = client.call(&alice, &pay_worker).submit().await.unwrap();
 
@@ -99,8 +139,346 @@ mod tests {
 
         let worker_balance = client.free_balance(worker.0.into()).await.unwrap();
 
+        let get_treasury = call_builder.get_treasury();
+        let treasury = client
+            .call(&alice, &get_treasury)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
         assert_eq!(paid_worker.destination, worker.0.into());
-        assert_eq!(worker_balance, value + worker_balance_before);
+        assert_eq!(paid_worker.id, job_id);
+        assert_eq!(paid_worker.fee, 5);
+        assert_eq!(worker_balance, value - 5 + worker_balance_before);
+        assert_eq!(treasury.balance(), 5);
+
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn cancel_job_request_refunds_escrow<Client: E2EBackend>(
+        mut client: Client,
+    ) -> E2EResult<()> {
+        let mut constructor = CatalogRef::new();
+        let alice = alice();
+        let contract = client
+            .instantiate("catalog", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let code = vec![1, 2, 3, 4];
+        let params = vec![vec![1, 2, 3, 4]];
+        let value = 100;
+
+        let job_request = JobRequest::new(&code, &params, b"add").with_timeout(0);
+        let job_id = job_request.id(alice.public_key().0.into(), 0);
+
+        let mut call_builder = contract.call_builder::<Catalog>();
+
+        let submit_job = call_builder.submit_job_request(job_request);
+        let _ = client
+            .call(&alice, &submit_job)
+            .value(value)
+            .submit()
+            .await
+            .unwrap();
+
+        let cancel_job = call_builder.cancel_job_request(job_id);
+        let response = client.call(&alice, &cancel_job).submit().await.unwrap();
+
+        let contract_emmitted_event = response
+            .events
+            .find_first::<ContractEmitted<DefaultEnvironment>>()
+            .unwrap()
+            .unwrap();
+
+        let job_cancelled =
+            <JobCancelled as Decode>::decode(&mut contract_emmitted_event.data.as_slice()).unwrap();
+
+        let contract_balance = client.free_balance(contract.account_id).await.unwrap();
+
+        assert_eq!(job_cancelled.who, alice.public_key().0.into());
+        assert_eq!(job_cancelled.value, value);
+        assert_eq!(contract_balance, 1);
+
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn submit_job_requests_emits_event_per_job<Client: E2EBackend>(
+        mut client: Client,
+    ) -> E2EResult<()> {
+        let mut constructor = CatalogRef::new();
+        let alice = alice();
+        let contract = client
+            .instantiate("catalog", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let code = vec![1, 2, 3, 4];
+        let job_requests = (0..3)
+            .map(|i| (JobRequest::new(&code, &vec![vec![i]], b"add"), 100))
+            .collect::<Vec<(JobRequest, u128)>>();
+
+        let mut call_builder = contract.call_builder::<Catalog>();
+
+        let submit_jobs = call_builder.submit_job_requests(job_requests);
+        let response = client
+            .call(&alice, &submit_jobs)
+            .value(300)
+            .submit()
+            .await
+            .unwrap();
+
+        let job_submitted_events = response
+            .events
+            .find::<ContractEmitted<DefaultEnvironment>>()
+            .map(|ev| {
+                <JobRequestSubmitted as Decode>::decode(&mut ev.unwrap().data.as_slice()).unwrap()
+            })
+            .collect::<Vec<JobRequestSubmitted>>();
+
+        let contract_balance = client.free_balance(contract.account_id).await.unwrap();
+
+        assert_eq!(job_submitted_events.len(), 3);
+        assert_eq!(job_submitted_events[2].nonce, 2);
+        assert_eq!(contract_balance, 300 + 1);
+
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn psp22_job_pays_worker_in_token<Client: E2EBackend>(
+        mut client: Client,
+    ) -> E2EResult<()> {
+        let alice = alice();
+        let bob = bob();
+        let worker = bob.public_key().to_account_id();
+        let value = 100;
+
+        let mut constructor = CatalogRef::new();
+        let contract = client
+            .instantiate("catalog", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let mut constructor = TokenRef::new(1_000);
+        let token = client
+            .instantiate("psp22_token", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let mut call_builder = contract.call_builder::<Catalog>();
+        let mut token_call_builder = token.call_builder::<Token>();
+
+        let approve = token_call_builder.approve(contract.account_id, value);
+        let _ = client.call(&alice, &approve).submit().await.unwrap();
+
+        let code = vec![1, 2, 3, 4];
+        let params = vec![vec![1, 2, 3, 4]];
+        let job_request =
+            JobRequest::new(&code, &params, b"add").with_token(token.account_id, value);
+        let job_id = job_request.id(alice.public_key().0.into(), 0);
+
+        let submit_job = call_builder.submit_job_request(job_request);
+        let _ = client.call(&alice, &submit_job).submit().await.unwrap();
+
+        let escrowed = token_call_builder.balance_of(contract.account_id);
+        let escrowed = client
+            .call(&alice, &escrowed)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
+        assert_eq!(escrowed, value);
+
+        let profile = WorkerProfile::new(
+            vec![ValueType::I32, ValueType::I64],
+            1 << 20,
+            1_000_000,
+            1,
+            vec![],
+            String::from("0.0.1"),
+        );
+        let register_worker = call_builder.register_worker(profile);
+        let _ = client
+            .call(&bob, &register_worker)
+            .value(MIN_WORKER_STAKE)
+            .submit()
+            .await
+            .unwrap();
+
+        let accept_job = call_builder.accept_job_request(job_id);
+        let _ = client.call(&bob, &accept_job).submit().await.unwrap();
+
+        let result_hash = result_hash(&[vec![24]]);
+        let salt = [9; 32];
+
+        let commit_result = call_builder.commit_result(job_id, seal_result(result_hash, salt));
+        let _ = client.call(&bob, &commit_result).submit().await.unwrap();
+
+        let reveal_result = call_builder.reveal_result(job_id, result_hash, salt);
+        let _ = client.call(&bob, &reveal_result).submit().await.unwrap();
+
+        let pay_worker = call_builder.pay_worker(job_id);
+        let response = client.call(&alice, &pay_worker).submit().await.unwrap();
+
+        let contract_emmitted_event = response
+            .events
+            .find_first::<ContractEmitted<DefaultEnvironment>>()
+            .unwrap()
+            .unwrap();
+
+        let paid_worker =
+            <PaidWorker as Decode>::decode(&mut contract_emmitted_event.data.as_slice()).unwrap();
+
+        let worker_balance = token_call_builder.balance_of(worker.0.into());
+        let worker_balance = client
+            .call(&alice, &worker_balance)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
+        let escrowed = token_call_builder.balance_of(contract.account_id);
+        let escrowed = client
+            .call(&alice, &escrowed)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
+        assert_eq!(paid_worker.token, Some(token.account_id));
+        assert_eq!(worker_balance, value);
+        assert_eq!(escrowed, 0);
+
+        Ok(())
+    }
+
+    #[ink_e2e::test]
+    async fn psp22_job_cancel_refunds_token<Client: E2EBackend>(
+        mut client: Client,
+    ) -> E2EResult<()> {
+        let alice = alice();
+        let value = 100;
+
+        let mut constructor = CatalogRef::new();
+        let contract = client
+            .instantiate("catalog", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let mut constructor = TokenRef::new(1_000);
+        let token = client
+            .instantiate("psp22_token", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let mut call_builder = contract.call_builder::<Catalog>();
+        let mut token_call_builder = token.call_builder::<Token>();
+
+        let approve = token_call_builder.approve(contract.account_id, value);
+        let _ = client.call(&alice, &approve).submit().await.unwrap();
+
+        let code = vec![1, 2, 3, 4];
+        let params = vec![vec![1, 2, 3, 4]];
+        let job_request = JobRequest::new(&code, &params, b"add")
+            .with_timeout(0)
+            .with_token(token.account_id, value);
+        let job_id = job_request.id(alice.public_key().0.into(), 0);
+
+        let submit_job = call_builder.submit_job_request(job_request);
+        let _ = client.call(&alice, &submit_job).submit().await.unwrap();
+
+        let requester_balance = token_call_builder.balance_of(alice.public_key().0.into());
+        let balance_after_submit = client
+            .call(&alice, &requester_balance)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
+        let cancel_job = call_builder.cancel_job_request(job_id);
+        let response = client.call(&alice, &cancel_job).submit().await.unwrap();
+
+        let contract_emmitted_event = response
+            .events
+            .find_first::<ContractEmitted<DefaultEnvironment>>()
+            .unwrap()
+            .unwrap();
+
+        let job_cancelled =
+            <JobCancelled as Decode>::decode(&mut contract_emmitted_event.data.as_slice()).unwrap();
+
+        let balance_after_cancel = client
+            .call(&alice, &requester_balance)
+            .dry_run()
+            .await
+            .unwrap()
+            .return_value();
+
+        assert_eq!(job_cancelled.value, value);
+        assert_eq!(balance_after_submit, 1_000 - value);
+        assert_eq!(balance_after_cancel, 1_000);
+
+        Ok(())
+    }
+
+    /// Benchmarks submitting jobs for a single requester, checking that the cost of a submission
+    /// does not grow with the number of jobs the requester already has. Submits thousands of
+    /// jobs, so it only runs when asked for with `--ignored`.
+    #[ink_e2e::test]
+    #[ignore]
+    async fn submit_job_cost_stays_flat_for_heavy_requester<Client: E2EBackend>(
+        mut client: Client,
+    ) -> E2EResult<()> {
+        const JOBS: u64 = 2_000;
+
+        let mut constructor = CatalogRef::new();
+        let alice = alice();
+        let contract = client
+            .instantiate("catalog", &alice, &mut constructor)
+            .submit()
+            .await
+            .unwrap();
+
+        let code = vec![1, 2, 3, 4];
+        let params = vec![vec![1, 2, 3, 4]];
+        let job_request = JobRequest::new(&code, &params, b"add");
+
+        let mut call_builder = contract.call_builder::<Catalog>();
+        let mut costs = Vec::new();
+
+        for _ in 0..JOBS {
+            let submit_job = call_builder.submit_job_request(job_request.clone());
+            let response = client
+                .call(&alice, &submit_job)
+                .value(100)
+                .submit()
+                .await
+                .unwrap();
+            let exec_result = response.dry_run.exec_result;
+
+            costs.push((
+                exec_result.gas_consumed.ref_time(),
+                exec_result.storage_deposit.charge_or_zero(),
+            ));
+        }
+
+        // The first submission also creates the requester's nonce, so compare against the second.
+        let (first_gas, first_deposit) = costs[1];
+        let (last_gas, last_deposit) = costs[costs.len() - 1];
+
+        assert!(last_gas <= first_gas + first_gas / 20);
+        assert_eq!(last_deposit, first_deposit);
 
         Ok(())
     }
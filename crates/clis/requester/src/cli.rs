@@ -1,4 +1,7 @@
-use crate::{commands::submit_job::SubmitJobCmd, config::Config};
+use crate::{
+    commands::{close_auction::CloseAuctionCmd, submit_job::SubmitJobCmd},
+    config::Config,
+};
 use clap::{Parser, Subcommand};
 use tracing::error;
 
@@ -12,6 +15,7 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 enum Command {
     SubmitJob(SubmitJobCmd),
+    CloseAuction(CloseAuctionCmd),
 }
 
 pub async fn run() {
@@ -20,6 +24,7 @@ pub async fn run() {
 
     let result = match args.command {
         Command::SubmitJob(cmd) => cmd.handle(config).await,
+        Command::CloseAuction(cmd) => cmd.handle(config).await,
     };
 
     if let Err(err) = result {
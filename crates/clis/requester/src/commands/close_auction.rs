@@ -0,0 +1,63 @@
+use crate::{config::Config, error::Error};
+use catalog::catalog::{AuctionClosed, HashId};
+use clap::Parser;
+use ink_env::DefaultEnvironment;
+use std::str::FromStr;
+use subxt::{utils::AccountId32, SubstrateConfig};
+use subxt_signer::sr25519::Keypair;
+use tracing::{info, instrument};
+use utils::services::contract_client::{Client, ContractClient};
+
+#[derive(Debug, Parser)]
+pub struct CloseAuctionCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Hex encoded id of an auctioned job whose reveal window has passed
+    #[arg(long)]
+    pub job_id: String,
+}
+
+impl CloseAuctionCmd {
+    #[instrument(skip_all)]
+    pub async fn handle(&self, config: Config) -> Result<(), Error> {
+        let job_id = self.job_id()?;
+
+        let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
+            &config.artifact_file_path,
+            &config.signer,
+            &config.url,
+        )
+        .await?;
+
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
+        let closed = contract_client
+            .write::<AuctionClosed, HashId>(contract_address, "close_auction", &job_id, 0)
+            .await?;
+
+        match closed.winner {
+            Some(winner) => info!("Auction won by {:?} for {}", winner, closed.price),
+            None => info!("No bid was revealed, the job was cancelled and refunded"),
+        }
+
+        Ok(())
+    }
+
+    fn job_id(&self) -> Result<HashId, Error> {
+        let mut job_id = HashId::default();
+        hex::decode_to_slice(self.job_id.trim_start_matches("0x"), &mut job_id)
+            .map_err(|_| Error::ParsingJobId)?;
+
+        Ok(job_id)
+    }
+}
@@ -1 +1,2 @@
+pub mod close_auction;
 pub mod submit_job;
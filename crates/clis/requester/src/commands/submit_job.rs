@@ -15,8 +15,9 @@ use utils::services::{
 
 #[derive(Debug, Parser)]
 pub struct SubmitJobCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
     #[arg(long)]
-    pub address: String,
+    pub address: Option<String>,
 
     #[arg(long)]
     pub code_path: String,
@@ -27,19 +28,38 @@ pub struct SubmitJobCmd {
     #[arg(long)]
     pub value: u128,
 
-    /// A comma seperated list of paramameters to pass to your function
+    /// A comma seperated list of paramameters to pass to your function. Repeat it to submit one
+    /// job per list, all in a single transaction
     #[arg(long)]
-    pub parameters: Option<String>,
+    pub parameters: Vec<String>,
+
+    /// Number of distinct workers that have to run the job and agree on its result
+    #[arg(long, default_value_t = 1)]
+    pub replicas: u8,
+
+    /// Number of milestones to release the escrow in, paying for each one as the worker reports
+    /// progress. Only jobs run by a single replica can use more than one
+    #[arg(long, default_value_t = 1)]
+    pub milestones: u8,
+
+    /// Address of a PSP22 token to pay `value` in instead of the native balance. The catalog is
+    /// approved to spend `value` of it before the job is submitted
+    #[arg(long)]
+    pub token: Option<String>,
 }
 
 impl SubmitJobCmd {
     #[instrument(skip_all)]
     pub async fn handle(&self, config: Config) -> Result<(), Error> {
-        let contract_address =
-            AccountId32::from_str(&self.address).map_err(|_| Error::ParsingContractAddress)?;
-
         let value: <DefaultEnvironment as Environment>::Balance = self.value;
 
+        let token = self
+            .token
+            .as_deref()
+            .map(AccountId32::from_str)
+            .transpose()
+            .map_err(|_| Error::ParsingTokenAddress)?;
+
         let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
             &config.artifact_file_path,
             &config.signer,
@@ -47,22 +67,40 @@ impl SubmitJobCmd {
         )
         .await?;
 
-        let job_handler_service = JobHandler::new(
-            &self.code_path,
-            self.parameters.clone(),
-            &self.function_name,
-        )
-        .await?;
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
+        let parameters = if self.parameters.is_empty() {
+            vec![None]
+        } else {
+            self.parameters.iter().cloned().map(Some).collect()
+        };
+
+        let mut job_handler_services = Vec::new();
+        for parameters in parameters {
+            job_handler_services
+                .push(JobHandler::new(&self.code_path, parameters, &self.function_name).await?);
+        }
 
         let (handle, network_client) = self.join_network(contract_address.to_string()).await?;
 
         let submit_job_controller = RequesterController::new(
             contract_address,
             value,
+            token,
+            self.replicas,
             contract_client,
-            job_handler_service,
+            job_handler_services,
             network_client,
-        );
+        )
+        .with_milestones(self.milestones);
 
         self.start(submit_job_controller, handle).await?;
 
@@ -1,11 +1,20 @@
-use catalog::catalog::{HashId, JobRequest, JobRequestSubmitted, PaidWorker};
+use catalog::catalog::{
+    result_hash, HashId, JobDisputed, JobRecord, JobRequest, JobRequestSubmitted, JobState,
+    MilestonePaid, Milestones, PaidWorker, ProgressReported, Reputation, WorkersAssigned,
+    DEFAULT_JOB_TIMEOUT, DISPUTE_BOND, PSP22_APPROVE_SELECTOR,
+};
 use clis::{Gossip, Request, Response};
-use codec::Encode;
-use ink_env::Environment;
+use codec::{Decode, Encode};
+use ink_env::{DefaultEnvironment, Environment};
+use std::cmp::Reverse;
 use subxt::{ext::futures::StreamExt, Config};
+use tokio::{
+    select,
+    time::{timeout_at, Duration, Instant},
+};
 use tracing::{error, info};
 use utils::services::{
-    contract_client::{ContractClient, ContractClientError},
+    contract_client::{verify_signature, ContractClient, ContractClientError},
     job::{
         job_handler::{JobHandlerService, JobHandlerServiceError},
         JobT, RawResultsT,
@@ -13,19 +22,30 @@ use utils::services::{
     p2p::{GossipMessageT, NetworkClient, NetworkClientError, NetworkIdT, RequestT, ResponseT},
 };
 
+/// How long to keep listening for job acceptances once enough workers have answered, so that
+/// better reputed workers still get a chance to be picked.
+const ACCEPTANCE_GRACE_PERIOD: Duration = Duration::from_secs(2);
+/// How long to wait for workers to accept a job, or to report its next milestone. Past it the
+/// job's deadline has passed on chain and workers can no longer take it or report progress.
+const JOB_TIMEOUT: Duration = Duration::from_millis(DEFAULT_JOB_TIMEOUT);
+
 pub struct RequesterController<C: Config, E: Environment, CC, JH, NC> {
     contract_address: <C as Config>::AccountId,
     value: <E as Environment>::Balance,
+    token: Option<<C as Config>::AccountId>,
+    replicas: u8,
+    milestones: u8,
     contract_client: CC,
-    job_handler_service: JH,
+    job_handler_services: Vec<JH>,
     network_client: NC,
 }
 
 impl<C, E, CC, JH, NC> RequesterController<C, E, CC, JH, NC>
 where
     C: Config,
+    C::AccountId: Encode + Decode + Send + Sync,
     E: Environment,
-    E::Balance: From<u128>,
+    E::Balance: From<u128> + Into<u128>,
     CC: ContractClient<C = C, E = E>,
     JH: JobHandlerService,
     NC: NetworkClient,
@@ -33,75 +53,220 @@ where
         + From<<CC as ContractClient>::Err>
         + From<<JH as JobHandlerService>::Err>,
 {
+    /// Creates a controller that submits one job per job handler, all in the same transaction,
+    /// each paying `value` to its workers.
     pub fn new(
         contract_address: <C as Config>::AccountId,
         value: <E as Environment>::Balance,
+        token: Option<<C as Config>::AccountId>,
+        replicas: u8,
         contract_client: CC,
-        job_handler_service: JH,
+        job_handler_services: Vec<JH>,
         network_client: NC,
     ) -> Self {
         Self {
             contract_address,
             value,
+            token,
+            replicas,
+            milestones: 1,
             contract_client,
-            job_handler_service,
+            job_handler_services,
             network_client,
         }
     }
 
+    /// Releases the job's escrow in `milestones` parts, paying for each one as the worker
+    /// reports it.
+    pub fn with_milestones(mut self, milestones: u8) -> Self {
+        self.milestones = milestones;
+        self
+    }
+
     pub async fn run(&self) -> Result<(), RequesterControllerError> {
-        let job = self.job_handler_service.build_job().await?;
-        let job_request = JobRequest::new(job.code_ref(), job.params_ref());
-        let job_id = job_request.id();
+        let mut jobs = Vec::new();
+        for job_handler_service in self.job_handler_services.iter() {
+            jobs.push(job_handler_service.build_job().await?);
+        }
+
+        let token = match self.token.clone() {
+            Some(token) => {
+                self.approve_token(token.clone(), jobs.len()).await?;
+
+                let token = <<DefaultEnvironment as Environment>::AccountId as Decode>::decode(
+                    &mut token.encode().as_slice(),
+                )
+                .map_err(ContractClientError::from)?;
+                Some(token)
+            }
+            None => None,
+        };
+
+        let job_requests = jobs
+            .iter()
+            .map(|job| {
+                let job_request =
+                    JobRequest::new(job.code_ref(), job.params_ref(), job.func_name_ref())
+                        .with_replicas(self.replicas)
+                        .with_milestones(self.milestones);
+
+                match token {
+                    Some(token) => job_request.with_token(token, self.value.into()),
+                    None => job_request,
+                }
+            })
+            .collect::<Vec<JobRequest>>();
 
-        self.submit_job(&job_request).await?;
-        let msg = self
-            .wait_for_job_acceptance(&job_request)
-            .await
-            .ok_or_else(|| RequesterControllerError::JobNeverAccepted)?;
+        let job_ids = self.submit_jobs(job_requests).await?;
+        let candidates = self.wait_for_job_acceptances(&job_ids).await;
 
-        self.send_job(msg.network_id(), job).await?;
-        self.wait_for_job_acknowledgement(job_id).await;
+        for ((job_id, job), candidates) in job_ids.iter().zip(jobs.iter()).zip(candidates) {
+            let workers = self.select_workers(candidates).await;
+
+            if workers.len() < usize::from(self.replicas) {
+                return Err(RequesterControllerError::JobNeverAccepted);
+            }
 
-        let (req_id, results, worker) = self
-            .wait_for_job_results(job_id)
-            .await
-            .ok_or_else(|| RequesterControllerError::ResultsNeverReceived)?;
-        self.send_result_acknowledgement(req_id, job_id).await?;
-        self.pay_worker(worker, job_id).await?;
+            self.assign_workers(*job_id, &workers).await?;
+            for (network_id, _) in workers {
+                self.send_job(network_id, job).await?;
+            }
+        }
+        self.wait_for_job_acknowledgements(&job_ids).await;
+
+        let results = select! {
+            results = self.wait_for_job_results(&job_ids) => results?,
+            Err(err) = self.approve_milestones(&job_ids) => return Err(err),
+        };
+
+        for ((job_id, results), job_handler_service) in job_ids
+            .iter()
+            .zip(results)
+            .zip(self.job_handler_services.iter())
+        {
+            if results.len() < usize::from(self.replicas) {
+                return Err(RequesterControllerError::ResultsNeverReceived);
+            }
 
-        self.display_results(results).await?;
+            let results = self.verify_results(*job_id, results).await?;
+            self.pay_worker(*job_id).await?;
+
+            self.display_results(job_handler_service, results).await?;
+        }
 
         Ok(())
     }
 
-    async fn submit_job(&self, job_request: &JobRequest) -> Result<(), RequesterControllerError> {
+    /// Lets the catalog pull the payment of `jobs` jobs from the requester's balance of
+    /// `token`.
+    async fn approve_token(
+        &self,
+        token: <C as Config>::AccountId,
+        jobs: usize,
+    ) -> Result<(), RequesterControllerError> {
+        let value: u128 = self.value.into();
+        let amount = value.saturating_mul(jobs as u128);
+
         self.contract_client
-            .write::<JobRequestSubmitted, JobRequest>(
-                self.contract_address.clone(),
-                "submit_job_request",
-                job_request,
-                self.value,
+            .write_selector(
+                token,
+                PSP22_APPROVE_SELECTOR,
+                &(self.contract_address.clone(), amount),
+                0_u128.into(),
             )
             .await?;
 
-        info!("Job Request Submitted!");
+        info!("Approved catalog to spend {} of token", amount);
+
         Ok(())
     }
 
-    async fn wait_for_job_acceptance(
+    /// Submits every job request in a single call and returns the ids of the jobs, in the
+    /// order of `job_requests`.
+    async fn submit_jobs(
         &self,
-        job_request: &JobRequest,
-    ) -> Option<<NC as NetworkClient>::GossipMessage> {
+        job_requests: Vec<JobRequest>,
+    ) -> Result<Vec<HashId>, RequesterControllerError> {
+        let amount: u128 = match self.token {
+            Some(_) => 0,
+            None => self.value.into(),
+        };
+        let value = amount.saturating_mul(job_requests.len() as u128);
+        let job_requests = job_requests
+            .into_iter()
+            .map(|job_request| (job_request, amount))
+            .collect::<Vec<(JobRequest, u128)>>();
+
+        let jobs_submitted = self
+            .contract_client
+            .write_batch::<JobRequestSubmitted, Vec<(JobRequest, u128)>>(
+                self.contract_address.clone(),
+                "submit_job_requests",
+                &job_requests,
+                value.into(),
+            )
+            .await?;
+
+        info!("{} Job Requests Submitted!", jobs_submitted.len());
+        Ok(jobs_submitted.iter().map(JobRequestSubmitted::id).collect())
+    }
+
+    /// Collects the workers that accepted each of the jobs in `ids`, until every job has enough
+    /// of them and the grace period has passed, or `JOB_TIMEOUT` has. Acceptances not signed by
+    /// the account they name, for the peer that published them, are ignored.
+    async fn wait_for_job_acceptances(
+        &self,
+        ids: &[HashId],
+    ) -> Vec<Vec<(<NC as NetworkClient>::NetworkId, Vec<u8>)>> {
+        let mut candidates = ids.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+        let mut grace_period_end = None;
+        let deadline = Instant::now() + JOB_TIMEOUT;
         let gossip_stream = self.network_client.gossip_msg_stream().await;
         tokio::pin!(gossip_stream);
 
-        while let Some(gsp_msg) = gossip_stream.next().await {
+        loop {
+            let end = grace_period_end.unwrap_or(deadline);
+            let Ok(Some(gsp_msg)) = timeout_at(end, gossip_stream.next()).await else {
+                break;
+            };
+
             let network_id = gsp_msg.network_id();
-            if let Ok(Gossip::JobAcceptance { job_id }) = Gossip::decode(gsp_msg.message_ref()) {
-                if job_id == job_request.id() {
+            if let Ok(Gossip::JobAcceptance {
+                job_id,
+                worker,
+                signature,
+            }) = Gossip::decode(gsp_msg.message_ref())
+            {
+                let Some(job_candidates) = ids
+                    .iter()
+                    .position(|id| *id == job_id)
+                    .map(|i| &mut candidates[i])
+                else {
+                    continue;
+                };
+
+                let payload = Gossip::acceptance_payload(&job_id, &network_id.to_vec());
+                if !verify_signature(&payload, &signature, &worker) {
+                    error!(
+                        "Ignoring job acceptance with an invalid signature from peer {}",
+                        network_id
+                    );
+                    continue;
+                }
+
+                if !job_candidates
+                    .iter()
+                    .any(|(peer, account)| *peer == network_id || *account == worker)
+                {
                     info!("Job acceptance received from peer: {}", network_id);
-                    return Some(gsp_msg);
+                    job_candidates.push((network_id, worker));
+
+                    let accepted = candidates
+                        .iter()
+                        .all(|job_candidates| job_candidates.len() >= usize::from(self.replicas));
+                    if accepted && grace_period_end.is_none() {
+                        grace_period_end = Some(Instant::now() + ACCEPTANCE_GRACE_PERIOD);
+                    }
                 }
             } else {
                 error!(
@@ -112,16 +277,93 @@ where
             }
         }
 
-        None
+        candidates
+    }
+
+    /// Picks the workers with the fewest failures, breaking ties by the number of jobs they
+    /// completed, among those that accepted the job.
+    async fn select_workers(
+        &self,
+        candidates: Vec<(<NC as NetworkClient>::NetworkId, Vec<u8>)>,
+    ) -> Vec<(<NC as NetworkClient>::NetworkId, Vec<u8>)> {
+        let mut ranked = Vec::new();
+
+        for (network_id, worker) in candidates {
+            let reputation = match self.worker_reputation(&worker).await {
+                Ok(reputation) => reputation,
+                Err(err) => {
+                    error!("Unable to read reputation of peer {}: {}", network_id, err);
+                    Reputation::default()
+                }
+            };
+            ranked.push((network_id, worker, reputation));
+        }
+
+        ranked.sort_by_key(|(_, _, reputation)| {
+            (reputation.failures(), Reverse(reputation.jobs_completed()))
+        });
+
+        ranked
+            .into_iter()
+            .take(usize::from(self.replicas))
+            .map(|(network_id, worker, _)| (network_id, worker))
+            .collect()
+    }
+
+    /// Restricts the job to the selected `workers` on chain, so no other worker can take one
+    /// of its replica slots.
+    async fn assign_workers(
+        &self,
+        job_id: HashId,
+        workers: &[(<NC as NetworkClient>::NetworkId, Vec<u8>)],
+    ) -> Result<(), RequesterControllerError> {
+        let accounts = workers
+            .iter()
+            .map(|(_, worker)| <C::AccountId as Decode>::decode(&mut worker.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(ContractClientError::from)?;
+
+        self.contract_client
+            .write::<WorkersAssigned, (HashId, Vec<C::AccountId>)>(
+                self.contract_address.clone(),
+                "assign_workers",
+                &(job_id, accounts),
+                0_u128.into(),
+            )
+            .await?;
+
+        info!("Assigned job to {} workers", workers.len());
+
+        Ok(())
+    }
+
+    async fn worker_reputation(
+        &self,
+        mut worker: &[u8],
+    ) -> Result<Reputation, RequesterControllerError> {
+        let who =
+            <C::AccountId as Decode>::decode(&mut worker).map_err(ContractClientError::from)?;
+
+        let worker = self
+            .contract_client
+            .worker_profile(self.contract_address.clone(), who)
+            .await?;
+
+        Ok(worker.reputation)
     }
 
     async fn send_job(
         &self,
         network_id: <NC as NetworkClient>::NetworkId,
-        job: impl JobT,
+        job: &impl JobT,
     ) -> Result<(), RequesterControllerError> {
         let who = self.network_client.get_local_network_id().await?;
-        let req = Request::build_job_req(job.into_parts(), who.to_vec());
+        let parts = (
+            job.code_ref().to_vec(),
+            job.params_ref().clone(),
+            job.func_name_ref().to_vec(),
+        );
+        let req = Request::build_job_req(parts, who.to_vec());
 
         self.network_client
             .send_request(network_id, req.encode())
@@ -132,15 +374,20 @@ where
         Ok(())
     }
 
-    async fn wait_for_job_acknowledgement(&self, id: HashId) {
+    async fn wait_for_job_acknowledgements(&self, ids: &[HashId]) {
+        let mut acknowledgements = vec![0; ids.len()];
         let resp_stream = self.network_client.resp_stream().await;
         tokio::pin!(resp_stream);
 
         while let Some(resp) = resp_stream.next().await {
             if let Ok(Response::AcknowledgeJob { job_id }) = Response::decode(resp.body_ref()) {
-                if job_id == id {
+                if let Some(i) = ids.iter().position(|id| *id == job_id) {
                     info!("Job has been accepted by a worker");
-                    break;
+                    acknowledgements[i] += 1;
+
+                    if acknowledgements.iter().all(|acks| *acks >= self.replicas) {
+                        break;
+                    }
                 }
             } else {
                 error!("Unable to decode response");
@@ -148,34 +395,178 @@ where
         }
     }
 
+    /// Collects the results workers send for each of the jobs in `ids`, until every job has
+    /// one from each of its replicas.
     async fn wait_for_job_results(
         &self,
-        id: HashId,
-    ) -> Option<(
-        <NC as NetworkClient>::Id,
-        <JH as JobHandlerService>::RawResults,
-        [u8; 32],
-    )> {
+        ids: &[HashId],
+    ) -> Result<Vec<Vec<(<JH as JobHandlerService>::RawResults, HashId)>>, RequesterControllerError>
+    {
+        let mut results = ids.iter().map(|_| Vec::new()).collect::<Vec<_>>();
         let req_stream = self.network_client.req_stream().await;
         tokio::pin!(req_stream);
+
         while let Some(req) = req_stream.next().await {
-            if let Ok(Request::Result {
-                result,
-                job_id,
-                worker,
-            }) = Request::decode(req.body_ref())
-            {
-                if job_id == id {
+            if let Ok(Request::Result { result, job_id }) = Request::decode(req.body_ref()) {
+                if let Some(i) = ids.iter().position(|id| *id == job_id) {
                     info!("Received results");
+                    let hash = result_hash(&result);
                     let result = <JH as JobHandlerService>::RawResults::from_vec(result);
-                    return Some((req.id(), result, worker));
+                    results[i].push((result, hash));
+                    self.send_result_acknowledgement(req.id(), job_id).await?;
+
+                    let received = results
+                        .iter()
+                        .all(|job_results| job_results.len() >= usize::from(self.replicas));
+                    if received {
+                        break;
+                    }
                 }
             } else {
                 error!("Unable to decode request");
             }
         }
 
-        None
+        Ok(results)
+    }
+
+    /// Pays for each milestone the workers of the jobs in `ids` report until all but the last
+    /// one of every job, which is paid with the result, have been released. Milestones reported
+    /// before the requester started listening are read from the catalog, and it gives up if no
+    /// job reports progress for `JOB_TIMEOUT`.
+    async fn approve_milestones(&self, ids: &[HashId]) -> Result<(), RequesterControllerError> {
+        if self.milestones <= 1 {
+            return Ok(());
+        }
+
+        let ev_stream = self
+            .contract_client
+            .contract_event_sub(self.contract_address.clone())
+            .await?;
+        tokio::pin!(ev_stream);
+
+        let mut released = Vec::new();
+        for id in ids {
+            released.push(self.pay_reported_milestones(*id).await?.saturating_add(1));
+        }
+
+        while !released.iter().all(|released| *released >= self.milestones) {
+            let ev = match timeout_at(Instant::now() + JOB_TIMEOUT, ev_stream.next()).await {
+                Ok(Some(ev)) => ev?,
+                Ok(None) => break,
+                Err(_) => return Err(RequesterControllerError::MilestonesNeverReported),
+            };
+
+            let Ok(progress) = self.contract_client.decode_event::<ProgressReported>(&ev) else {
+                continue;
+            };
+
+            let Some(i) = ids.iter().position(|id| *id == progress.id) else {
+                continue;
+            };
+
+            info!("Worker reported milestone {}", progress.milestone);
+            released[i] = self
+                .pay_reported_milestones(progress.id)
+                .await?
+                .saturating_add(1);
+        }
+
+        Ok(())
+    }
+
+    /// Pays every milestone of `job_id` its worker reported that hasn't been paid yet, and
+    /// returns how many have been paid in total.
+    async fn pay_reported_milestones(
+        &self,
+        job_id: HashId,
+    ) -> Result<u8, RequesterControllerError> {
+        let milestones = self
+            .contract_client
+            .read::<Option<Milestones>, HashId>(
+                self.contract_address.clone(),
+                "get_milestones",
+                &job_id,
+            )
+            .await?
+            .ok_or(RequesterControllerError::MilestonesNeverReported)?;
+
+        for _ in milestones.released()..milestones.reported() {
+            self.pay_milestone(job_id).await?;
+        }
+
+        Ok(milestones.reported())
+    }
+
+    async fn pay_milestone(&self, job_id: HashId) -> Result<(), RequesterControllerError> {
+        let value: <E as Environment>::Balance = 0_u128.into();
+
+        let milestone_paid = self
+            .contract_client
+            .write::<MilestonePaid, HashId>(
+                self.contract_address.clone(),
+                "pay_milestone",
+                &job_id,
+                value,
+            )
+            .await?;
+
+        info!(
+            "Paid milestone {} of {}",
+            milestone_paid.milestone, self.milestones
+        );
+
+        Ok(())
+    }
+
+    async fn verify_results(
+        &self,
+        job_id: HashId,
+        results: Vec<(<JH as JobHandlerService>::RawResults, HashId)>,
+    ) -> Result<<JH as JobHandlerService>::RawResults, RequesterControllerError> {
+        let record = self
+            .contract_client
+            .job(self.contract_address.clone(), job_id)
+            .await?;
+
+        let agreed = match record.as_ref().map(JobRecord::state) {
+            Some(JobState::ResultCommitted { result_hash }) => Some(*result_hash),
+            _ => None,
+        };
+
+        let result = results
+            .into_iter()
+            .find(|(_, hash)| Some(*hash) == agreed)
+            .map(|(result, _)| result);
+
+        match (result, record.as_ref().map(JobRecord::state)) {
+            (Some(result), _) => {
+                info!("Results match on chain commitment");
+                Ok(result)
+            }
+            (None, Some(JobState::ResultCommitted { .. })) => {
+                self.dispute_job(job_id).await?;
+                Err(RequesterControllerError::ResultMismatch)
+            }
+            (None, _) => Err(RequesterControllerError::ResultMismatch),
+        }
+    }
+
+    async fn dispute_job(&self, job_id: HashId) -> Result<(), RequesterControllerError> {
+        let value: <E as Environment>::Balance = DISPUTE_BOND.into();
+
+        self.contract_client
+            .write::<JobDisputed, HashId>(
+                self.contract_address.clone(),
+                "dispute_job",
+                &job_id,
+                value,
+            )
+            .await?;
+
+        info!("Job disputed");
+
+        Ok(())
     }
 
     async fn send_result_acknowledgement(
@@ -196,9 +587,10 @@ where
 
     async fn display_results(
         &self,
+        job_handler_service: &JH,
         results: <JH as JobHandlerService>::RawResults,
     ) -> Result<(), RequesterControllerError> {
-        let parsed_results = self.job_handler_service.unpack_results(results).await?;
+        let parsed_results = job_handler_service.unpack_results(results).await?;
 
         let results = format!("Results: {}", parsed_results);
         let stars = vec!["*"; results.len() + 4].join("");
@@ -208,19 +600,14 @@ where
         Ok(())
     }
 
-    async fn pay_worker(
-        &self,
-        worker: [u8; 32],
-        job_id: HashId,
-    ) -> Result<(), RequesterControllerError> {
-        let args = (worker, job_id);
+    async fn pay_worker(&self, job_id: HashId) -> Result<(), RequesterControllerError> {
         let value: <E as Environment>::Balance = 0_u128.into();
 
         self.contract_client
-            .write::<PaidWorker, ([u8; 32], HashId)>(
+            .write::<PaidWorker, HashId>(
                 self.contract_address.clone(),
                 "pay_worker",
-                &args,
+                &job_id,
                 value,
             )
             .await?;
@@ -256,4 +643,10 @@ pub enum RequesterControllerError {
 
     #[error("")]
     ResultsNeverReceived,
+
+    #[error("Workers stopped reporting milestones before the job finished")]
+    MilestonesNeverReported,
+
+    #[error("Received results do not match the hash committed on chain")]
+    ResultMismatch,
 }
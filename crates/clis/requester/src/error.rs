@@ -6,6 +6,12 @@ pub enum Error {
         source: utils::services::contract_client::Error,
     },
 
+    #[error("{source}")]
+    ContractClient {
+        #[from]
+        source: utils::services::contract_client::ContractClientError,
+    },
+
     #[error("{source}")]
     RequesterController {
         #[from]
@@ -33,6 +39,15 @@ pub enum Error {
     #[error("unable to parse contract address from provided string")]
     ParsingContractAddress,
 
+    #[error("no contract address provided and the node has no genesis catalog")]
+    CatalogNotDeployed,
+
+    #[error("unable to parse token address from provided string")]
+    ParsingTokenAddress,
+
+    #[error("unable to parse job id from provided string")]
+    ParsingJobId,
+
     #[error("")]
     NetworkHandlerStopped,
 }
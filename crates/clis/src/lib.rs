@@ -3,7 +3,14 @@ use codec::{Decode, Encode};
 
 #[derive(Encode, Decode)]
 pub enum Gossip {
-    JobAcceptance { job_id: HashId },
+    /// A worker's offer to run a job. `signature` is the `worker` account's signature over
+    /// `Gossip::acceptance_payload` for the peer publishing it, so peers can't offer on behalf
+    /// of accounts they don't own.
+    JobAcceptance {
+        job_id: HashId,
+        worker: Vec<u8>,
+        signature: Vec<u8>,
+    },
 }
 
 #[derive(Encode, Decode)]
@@ -17,7 +24,6 @@ pub enum Request {
     Result {
         result: Vec<Vec<u8>>,
         job_id: HashId,
-        worker: [u8; 32],
     },
 }
 
@@ -32,6 +38,11 @@ impl Gossip {
         let res = <Gossip as Decode>::decode(&mut msg)?;
         Ok(res)
     }
+
+    /// Bytes a worker signs to offer to run `job_id` from the network peer `peer`.
+    pub fn acceptance_payload(job_id: &HashId, peer: &[u8]) -> Vec<u8> {
+        (job_id, peer).encode()
+    }
 }
 
 impl Request {
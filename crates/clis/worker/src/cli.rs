@@ -1,4 +1,9 @@
-use crate::{commands::start::StartCmd, config::Config};
+use crate::{
+    commands::{
+        bid::BidCmd, claim_payment::ClaimPaymentCmd, reveal_bid::RevealBidCmd, start::StartCmd,
+    },
+    config::Config,
+};
 use clap::{Parser, Subcommand};
 use tracing::error;
 
@@ -12,6 +17,9 @@ pub struct Cli {
 #[derive(Subcommand, Debug)]
 enum Command {
     Start(StartCmd),
+    ClaimPayment(ClaimPaymentCmd),
+    Bid(BidCmd),
+    RevealBid(RevealBidCmd),
 }
 
 pub async fn run() {
@@ -20,6 +28,9 @@ pub async fn run() {
 
     let result = match args.command {
         Command::Start(cmd) => cmd.handle(config).await,
+        Command::ClaimPayment(cmd) => cmd.handle(config).await,
+        Command::Bid(cmd) => cmd.handle(config).await,
+        Command::RevealBid(cmd) => cmd.handle(config).await,
     };
 
     if let Err(err) = result {
@@ -0,0 +1,75 @@
+use crate::{config::Config, error::Error};
+use catalog::catalog::{seal_bid, BidSubmitted, HashId};
+use clap::Parser;
+use ink_env::DefaultEnvironment;
+use std::str::FromStr;
+use subxt::{utils::AccountId32, SubstrateConfig};
+use subxt_signer::sr25519::Keypair;
+use tracing::{info, instrument};
+use utils::services::contract_client::{Client, ContractClient};
+
+#[derive(Debug, Parser)]
+pub struct BidCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Hex encoded id of an auctioned job still taking bids
+    #[arg(long)]
+    pub job_id: String,
+
+    /// Price to run the job for, which stays hidden until the bid is revealed
+    #[arg(long)]
+    pub price: u128,
+}
+
+impl BidCmd {
+    #[instrument(skip_all)]
+    pub async fn handle(&self, config: Config) -> Result<(), Error> {
+        let job_id = parse_hash(&self.job_id).ok_or(Error::ParsingJobId)?;
+        let salt: HashId = rand::random();
+
+        let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
+            &config.artifact_file_path,
+            &config.signer,
+            &config.url,
+        )
+        .await?;
+
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
+        contract_client
+            .write::<BidSubmitted, (HashId, HashId)>(
+                contract_address,
+                "submit_bid",
+                &(job_id, seal_bid(self.price, salt)),
+                0,
+            )
+            .await?;
+
+        info!(
+            "Bid submitted, reveal it once bidding ends with `reveal-bid --job-id {} --price {} --salt 0x{}`",
+            self.job_id,
+            self.price,
+            hex::encode(salt)
+        );
+
+        Ok(())
+    }
+}
+
+/// Parses a hex encoded job id or salt, with or without its `0x` prefix.
+pub fn parse_hash(hash: &str) -> Option<HashId> {
+    let mut parsed = HashId::default();
+    hex::decode_to_slice(hash.trim_start_matches("0x"), &mut parsed).ok()?;
+
+    Some(parsed)
+}
@@ -0,0 +1,67 @@
+use crate::{config::Config, error::Error};
+use catalog::catalog::{HashId, PaidWorker};
+use clap::Parser;
+use codec::Encode;
+use ink_env::DefaultEnvironment;
+use std::str::FromStr;
+use subxt::{utils::AccountId32, SubstrateConfig};
+use subxt_signer::sr25519::Keypair;
+use tracing::{info, instrument};
+use utils::services::contract_client::{Client, ContractClient};
+
+#[derive(Debug, Parser)]
+pub struct ClaimPaymentCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Hex encoded id of a job whose committed result the requester neither paid nor disputed
+    #[arg(long)]
+    pub job_id: String,
+}
+
+impl ClaimPaymentCmd {
+    #[instrument(skip_all)]
+    pub async fn handle(&self, config: Config) -> Result<(), Error> {
+        let job_id = self.job_id()?;
+
+        let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
+            &config.artifact_file_path,
+            &config.signer,
+            &config.url,
+        )
+        .await?;
+
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
+        // Every worker that agreed on the result is paid in the same call, so pick the event
+        // paying the signer.
+        let who = contract_client.account_id().encode();
+        let paid = contract_client
+            .write_batch::<PaidWorker, HashId>(contract_address, "claim_payment", &job_id, 0)
+            .await?
+            .into_iter()
+            .find(|paid| paid.destination.encode() == who)
+            .ok_or(Error::PaymentNotFound)?;
+
+        info!("Claimed {} for job", paid.value);
+
+        Ok(())
+    }
+
+    fn job_id(&self) -> Result<HashId, Error> {
+        let mut job_id = HashId::default();
+        hex::decode_to_slice(self.job_id.trim_start_matches("0x"), &mut job_id)
+            .map_err(|_| Error::ParsingJobId)?;
+
+        Ok(job_id)
+    }
+}
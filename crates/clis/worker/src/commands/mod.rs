@@ -1 +1,4 @@
+pub mod bid;
+pub mod claim_payment;
+pub mod reveal_bid;
 pub mod start;
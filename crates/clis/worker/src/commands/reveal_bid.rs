@@ -0,0 +1,66 @@
+use crate::{commands::bid::parse_hash, config::Config, error::Error};
+use catalog::catalog::{BidRevealed, HashId};
+use clap::Parser;
+use ink_env::DefaultEnvironment;
+use std::str::FromStr;
+use subxt::{utils::AccountId32, SubstrateConfig};
+use subxt_signer::sr25519::Keypair;
+use tracing::{info, instrument};
+use utils::services::contract_client::{Client, ContractClient};
+
+#[derive(Debug, Parser)]
+pub struct RevealBidCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
+    #[arg(long)]
+    pub address: Option<String>,
+
+    /// Hex encoded id of the auctioned job the bid was submitted for
+    #[arg(long)]
+    pub job_id: String,
+
+    /// Price the bid was submitted with
+    #[arg(long)]
+    pub price: u128,
+
+    /// Hex encoded salt `bid` sealed the price with
+    #[arg(long)]
+    pub salt: String,
+}
+
+impl RevealBidCmd {
+    #[instrument(skip_all)]
+    pub async fn handle(&self, config: Config) -> Result<(), Error> {
+        let job_id = parse_hash(&self.job_id).ok_or(Error::ParsingJobId)?;
+        let salt = parse_hash(&self.salt).ok_or(Error::ParsingSalt)?;
+
+        let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
+            &config.artifact_file_path,
+            &config.signer,
+            &config.url,
+        )
+        .await?;
+
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
+        let revealed = contract_client
+            .write::<BidRevealed, (HashId, u128, HashId)>(
+                contract_address,
+                "reveal_bid",
+                &(job_id, self.price, salt),
+                0,
+            )
+            .await?;
+
+        info!("Revealed bid of {} for job", revealed.price);
+
+        Ok(())
+    }
+}
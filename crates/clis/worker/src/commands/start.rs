@@ -14,15 +14,30 @@ use utils::services::{
 
 #[derive(Debug, Parser)]
 pub struct StartCmd {
+    /// Address of the catalog contract, defaults to the one the node deployed at genesis
     #[arg(long)]
-    pub address: String,
+    pub address: Option<String>,
+
+    /// Balance to lock as stake when registering the worker with the catalog
+    #[arg(long)]
+    pub stake: Option<u128>,
+
+    /// Maximum linear memory, in bytes, advertised to requesters
+    #[arg(long, default_value_t = 64 * 1024 * 1024)]
+    pub max_memory: u64,
+
+    /// Maximum fuel a single job may consume
+    #[arg(long, default_value_t = 10_000_000)]
+    pub max_fuel: u64,
+
+    /// Price charged per unit of fuel consumed by a job
+    #[arg(long, default_value_t = 1)]
+    pub price_per_fuel: u128,
 }
 
 impl StartCmd {
     #[instrument(skip_all)]
     pub async fn handle(&self, config: Config) -> Result<(), Error> {
-        let contract_address =
-            AccountId32::from_str(&self.address).map_err(|_| Error::ParsingContractAddress)?;
         let contract_client = Client::<SubstrateConfig, DefaultEnvironment, Keypair>::new(
             &config.artifact_file_path,
             &config.signer,
@@ -30,24 +45,38 @@ impl StartCmd {
         )
         .await?;
 
+        let contract_address = match &self.address {
+            Some(address) => {
+                AccountId32::from_str(address).map_err(|_| Error::ParsingContractAddress)?
+            }
+            None => contract_client
+                .catalog_address()
+                .await?
+                .ok_or(Error::CatalogNotDeployed)?,
+        };
+
         let (handle, network_client) = self.join_network(contract_address.to_string()).await?;
 
         let job_runner = WasmJobRunner::new();
 
         let worker_controller: WorkerController<
             SubstrateConfig,
-            Keypair,
             Client<'_, SubstrateConfig, DefaultEnvironment, Keypair>,
             NodeClient,
             WasmJobRunner,
         > = WorkerController::new(
             contract_address,
-            config.signer.clone(),
             contract_client,
             network_client,
             job_runner,
         );
 
+        if let Some(stake) = self.stake {
+            worker_controller
+                .register(stake, self.max_memory, self.max_fuel, self.price_per_fuel)
+                .await?;
+        }
+
         self.start(worker_controller, handle).await?;
 
         Ok(())
@@ -68,7 +97,6 @@ impl StartCmd {
         &self,
         controller: WorkerController<
             SubstrateConfig,
-            Keypair,
             Client<'_, SubstrateConfig, DefaultEnvironment, Keypair>,
             NodeClient,
             WasmJobRunner,
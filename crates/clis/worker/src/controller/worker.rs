@@ -1,40 +1,50 @@
-use catalog::catalog::{HashId, JobRequest, JobRequestSubmitted};
+use catalog::catalog::{
+    result_hash, seal_result, HashId, JobAccepted, JobRecord, JobRequest, JobRequestSubmitted,
+    JobResultCommitted, JobResultRevealed, JobState, ProgressReported, ValueType, WorkerProfile,
+    WorkerRegistered,
+};
 use clis::{Gossip, Request, Response};
 use codec::Encode;
+use ink_env::Environment;
 use std::fmt::Display;
-use subxt::{ext::futures::StreamExt, tx::Signer, Config};
+use subxt::{ext::futures::StreamExt, Config};
 use tokio::{
-    pin,
+    pin, select,
     time::{sleep, Duration},
 };
 use tracing::{error, info};
-use utils::{
-    services::{
-        contract_client::{ContractClient, ContractClientError, ContractEmittedT},
-        job::{
-            job_runner::{WasmJobRunnerService, WasmJobRunnerServiceError},
-            JobT, RawResultsT,
-        },
-        p2p::{NetworkClient, NetworkClientError, NetworkIdT, RequestT, ResponseT},
+use utils::services::{
+    contract_client::{ContractClient, ContractClientError, ContractEmittedT},
+    job::{
+        job_runner::{WasmJobRunnerService, WasmJobRunnerServiceError},
+        JobT, RawResultsT,
     },
-    Wallet,
+    p2p::{NetworkClient, NetworkClientError, NetworkIdT, RequestT, ResponseT},
 };
 
-pub struct WorkerController<C: Config, S: Signer<C>, CC, NC, JR> {
+/// Wasm value types the job runner knows how to decode params and encode results for.
+const SUPPORTED_VALUE_TYPES: [ValueType; 2] = [ValueType::I32, ValueType::I64];
+
+/// How often a worker waiting for a job checks whether the requester picked other workers.
+const JOB_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long a worker waits for the requester to send a job it offered to run.
+const JOB_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub struct WorkerController<C: Config, CC, NC, JR> {
     contract_address: <C as Config>::AccountId,
-    signer: S,
     contract_client: CC,
     network_client: NC,
     job_runner: JR,
 }
 
-impl<C, S, CC, NC, JR> WorkerController<C, S, CC, NC, JR>
+impl<C, CC, NC, JR> WorkerController<C, CC, NC, JR>
 where
     C: Config,
     <C as Config>::AccountId: Display,
-    S: Signer<C> + Wallet,
     CC: ContractClient<C = C>,
     CC::Err: Display,
+    <CC::E as Environment>::Balance: From<u128>,
     NC: NetworkClient,
     JR: WasmJobRunnerService,
     WorkerControllerError: From<<NC as NetworkClient>::Err>
@@ -43,20 +53,49 @@ where
 {
     pub fn new(
         contract_address: <C as Config>::AccountId,
-        signer: S,
         contract_client: CC,
         network_client: NC,
         job_runner: JR,
     ) -> Self {
         Self {
             contract_address,
-            signer,
             contract_client,
             network_client,
             job_runner,
         }
     }
 
+    pub async fn register(
+        &self,
+        stake: <CC::E as Environment>::Balance,
+        max_memory: u64,
+        max_fuel: u64,
+        price_per_fuel: u128,
+    ) -> Result<(), WorkerControllerError> {
+        let peer_id = self.network_client.get_local_network_id().await?.to_vec();
+        let profile = WorkerProfile::new(
+            SUPPORTED_VALUE_TYPES.to_vec(),
+            max_memory,
+            max_fuel,
+            price_per_fuel,
+            peer_id,
+            env!("CARGO_PKG_VERSION").to_string(),
+        );
+
+        self.contract_client
+            .write::<WorkerRegistered, WorkerProfile>(
+                self.contract_address.clone(),
+                "register_worker",
+                &profile,
+                stake,
+            )
+            .await?;
+
+        info!("Worker registered");
+
+        Ok(())
+    }
+
     pub async fn listen(&self) -> Result<(), WorkerControllerError> {
         info!("Starting Worker Controller");
 
@@ -79,7 +118,7 @@ where
     async fn handle_event(&self, ev: <CC as ContractClient>::ContractEmitted) {
         let res = if let Ok(job_request) = self
             .contract_client
-            .decode_event::<JobRequestSubmitted>(ev.data_ref())
+            .decode_event::<JobRequestSubmitted>(&ev)
         {
             self.handle_job_request(job_request).await
         } else {
@@ -95,15 +134,55 @@ where
         &self,
         job_request: JobRequestSubmitted,
     ) -> Result<(), WorkerControllerError> {
+        let supported = job_request
+            .requirements
+            .value_types()
+            .iter()
+            .all(|value_type| SUPPORTED_VALUE_TYPES.contains(value_type));
+
+        if !supported {
+            info!("Skipping job with unsupported value types");
+            return Ok(());
+        }
+
+        if job_request.bidding_ends.is_some() {
+            info!(
+                "Skipping auctioned job 0x{}, bid on it with the `bid` command",
+                hex::encode(job_request.id())
+            );
+            return Ok(());
+        }
+
+        let who = self.contract_client.account_id().encode();
+        let allowed = job_request.allowed_workers.is_empty()
+            || job_request
+                .allowed_workers
+                .iter()
+                .any(|worker| worker.encode() == who);
+
+        if !allowed {
+            info!("Skipping job restricted to other workers");
+            return Ok(());
+        }
+
         self.accept_job_request(&job_request).await?;
-        let (id, job, who) = self
-            .wait_for_job(job_request.id())
-            .await
-            .ok_or_else(|| WorkerControllerError::JobNeverSent)?;
+        let Some((id, job, who)) = self.wait_for_job(&job_request).await? else {
+            info!("Job was given to other workers");
+            return Ok(());
+        };
+        self.claim_job(&job_request).await?;
         self.acknowledge_job_acceptance(id, job_request.id())
             .await?;
 
-        let result = self.start_job(job).await?;
+        let result = self.run_job(&job_request, job).await?.to_vec();
+        let result_hash = result_hash(&result);
+        let salt: HashId = rand::random();
+
+        self.commit_result(job_request.id(), seal_result(result_hash, salt))
+            .await?;
+        self.wait_for_reveal_phase(job_request.id()).await?;
+        self.reveal_result(job_request.id(), result_hash, salt)
+            .await?;
         self.send_result(result, job_request.id(), who).await?;
         self.wait_for_result_acknowledgement(job_request.id()).await;
 
@@ -115,7 +194,16 @@ where
         job_request: &JobRequestSubmitted,
     ) -> Result<(), WorkerControllerError> {
         let job_id = job_request.id();
-        let msg = Gossip::JobAcceptance { job_id };
+        let worker = self.contract_client.account_id().encode();
+        let peer = self.network_client.get_local_network_id().await?.to_vec();
+        let signature = self
+            .contract_client
+            .sign(&Gossip::acceptance_payload(&job_id, &peer));
+        let msg = Gossip::JobAcceptance {
+            job_id,
+            worker,
+            signature,
+        };
         let topic = self.contract_address.to_string();
 
         self.wait_for_gossip_peers().await?;
@@ -140,18 +228,37 @@ where
         Ok(())
     }
 
+    /// Waits for the requester to send the job, which it only does to the workers it picks
+    /// among those that offered to run it. Gives up once the job no longer takes workers or
+    /// after `JOB_WAIT_TIMEOUT`, so a worker that wasn't picked moves on to the next job.
     async fn wait_for_job(
         &self,
-        id: HashId,
-    ) -> Option<(
-        <NC as NetworkClient>::Id,
-        <JR as WasmJobRunnerService>::Job,
-        <NC as NetworkClient>::NetworkId,
-    )> {
+        job_request: &JobRequestSubmitted,
+    ) -> Result<
+        Option<(
+            <NC as NetworkClient>::Id,
+            <JR as WasmJobRunnerService>::Job,
+            <NC as NetworkClient>::NetworkId,
+        )>,
+        WorkerControllerError,
+    > {
         let req_stream = self.network_client.req_stream().await;
         tokio::pin!(req_stream);
+        let timeout = sleep(JOB_WAIT_TIMEOUT);
+        tokio::pin!(timeout);
+
+        loop {
+            let req = select! {
+                req = req_stream.next() => req.ok_or(WorkerControllerError::JobNeverSent)?,
+                _ = sleep(JOB_POLL_INTERVAL) => {
+                    if self.job_taken(job_request.id()).await? {
+                        return Ok(None);
+                    }
+                    continue;
+                }
+                _ = &mut timeout => return Ok(None),
+            };
 
-        while let Some(req) = req_stream.next().await {
             if let Ok(Request::Job {
                 code,
                 params,
@@ -159,21 +266,56 @@ where
                 who,
             }) = Request::decode(req.body_ref())
             {
-                if JobRequest::hash(&code, &params) == id {
+                let id = JobRequest::new(&code, &params, &func_name)
+                    .id(job_request.who, job_request.nonce);
+
+                if id == job_request.id() {
                     info!("Job received!");
 
                     let job: <JR as WasmJobRunnerService>::Job =
                         JobT::from_parts(code, params, func_name);
                     let id = req.id();
                     let who = <NC as NetworkClient>::NetworkId::from_bytes(&who);
-                    return Some((id, job, who));
+                    return Ok(Some((id, job, who)));
                 }
             } else {
                 error!("Unable to decode request: {:?}", req.body_ref());
             }
         }
+    }
+
+    /// Whether the job stopped taking workers, either because the requester's picks filled
+    /// every replica or because it was cancelled or expired.
+    async fn job_taken(&self, job_id: HashId) -> Result<bool, WorkerControllerError> {
+        let record = self
+            .contract_client
+            .job(self.contract_address.clone(), job_id)
+            .await?;
 
-        None
+        Ok(!matches!(
+            record.as_ref().map(JobRecord::state),
+            Some(JobState::Submitted)
+        ))
+    }
+
+    async fn claim_job(
+        &self,
+        job_request: &JobRequestSubmitted,
+    ) -> Result<(), WorkerControllerError> {
+        let value: <CC::E as Environment>::Balance = 0_u128.into();
+
+        self.contract_client
+            .write::<JobAccepted, HashId>(
+                self.contract_address.clone(),
+                "accept_job_request",
+                &job_request.id(),
+                value,
+            )
+            .await?;
+
+        info!("Job accepted on chain");
+
+        Ok(())
     }
 
     async fn acknowledge_job_acceptance(
@@ -199,18 +341,123 @@ where
         Ok(result)
     }
 
+    /// Runs the job, reporting progress on every milestone but the last, which is paid with
+    /// the result. The job runner cannot tell how far along a job is, so milestones are reported
+    /// evenly spread over the job's timeout for as long as it is still running.
+    async fn run_job(
+        &self,
+        job_request: &JobRequestSubmitted,
+        job: <JR as WasmJobRunnerService>::Job,
+    ) -> Result<<JR as WasmJobRunnerService>::RawResults, WorkerControllerError> {
+        let job = self.start_job(job);
+        pin!(job);
+
+        let period =
+            Duration::from_millis(job_request.timeout / u64::from(job_request.milestones.max(1)));
+        let mut reported = 1;
+
+        loop {
+            select! {
+                result = &mut job => return result,
+                _ = sleep(period), if reported < job_request.milestones => {
+                    if let Err(err) = self.report_progress(job_request.id()).await {
+                        error!("Unable to report progress: {}", err);
+                    }
+                    reported += 1;
+                }
+            }
+        }
+    }
+
+    async fn report_progress(&self, job_id: HashId) -> Result<(), WorkerControllerError> {
+        let value: <CC::E as Environment>::Balance = 0_u128.into();
+
+        let progress = self
+            .contract_client
+            .write::<ProgressReported, HashId>(
+                self.contract_address.clone(),
+                "report_progress",
+                &job_id,
+                value,
+            )
+            .await?;
+
+        info!("Reported milestone {}", progress.milestone);
+
+        Ok(())
+    }
+
+    async fn commit_result(
+        &self,
+        job_id: HashId,
+        commitment: HashId,
+    ) -> Result<(), WorkerControllerError> {
+        let args = (job_id, commitment);
+        let value: <CC::E as Environment>::Balance = 0_u128.into();
+
+        self.contract_client
+            .write::<JobResultCommitted, (HashId, HashId)>(
+                self.contract_address.clone(),
+                "commit_result",
+                &args,
+                value,
+            )
+            .await?;
+
+        info!("Result committed on chain");
+
+        Ok(())
+    }
+
+    async fn wait_for_reveal_phase(&self, job_id: HashId) -> Result<(), WorkerControllerError> {
+        loop {
+            let record = self
+                .contract_client
+                .job(self.contract_address.clone(), job_id)
+                .await?;
+
+            match record.as_ref().map(JobRecord::state) {
+                Some(JobState::Accepted) => {
+                    info!("Waiting for replicas to commit");
+                    sleep(Duration::from_millis(500)).await;
+                }
+                // Replicas can still reveal after a quorum of them already has.
+                Some(JobState::Revealing) | Some(JobState::ResultCommitted { .. }) => return Ok(()),
+                _ => return Err(WorkerControllerError::RevealAborted),
+            }
+        }
+    }
+
+    async fn reveal_result(
+        &self,
+        job_id: HashId,
+        result_hash: HashId,
+        salt: HashId,
+    ) -> Result<(), WorkerControllerError> {
+        let args = (job_id, result_hash, salt);
+        let value: <CC::E as Environment>::Balance = 0_u128.into();
+
+        self.contract_client
+            .write::<JobResultRevealed, (HashId, HashId, HashId)>(
+                self.contract_address.clone(),
+                "reveal_result",
+                &args,
+                value,
+            )
+            .await?;
+
+        info!("Result revealed on chain");
+
+        Ok(())
+    }
+
     async fn send_result(
         &self,
-        result: <JR as WasmJobRunnerService>::RawResults,
+        result: Vec<Vec<u8>>,
         job_id: HashId,
         who: <NC as NetworkClient>::NetworkId,
     ) -> Result<(), WorkerControllerError> {
-        let worker = self.signer.public_key();
-        let req = Request::Result {
-            result: result.to_vec(),
-            job_id,
-            worker,
-        };
+        let req = Request::Result { result, job_id };
 
         self.network_client.send_request(who, req.encode()).await?;
         info!("Results sent");
@@ -256,6 +503,9 @@ pub enum WorkerControllerError {
     #[error("Unable to decode Contract Emitted event: {data:?}")]
     DecodeContractEvent { data: Vec<u8> },
 
-    #[error("")]
+    #[error("Request stream ended before the job was sent")]
     JobNeverSent,
+
+    #[error("Job left the commit phase before its result could be revealed")]
+    RevealAborted,
 }
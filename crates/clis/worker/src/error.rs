@@ -24,9 +24,27 @@ pub enum Error {
         source: tokio::task::JoinError,
     },
 
+    #[error("{source}")]
+    ContractClient {
+        #[from]
+        source: utils::services::contract_client::ContractClientError,
+    },
+
     #[error("unable to parse contract address from provided string")]
     ParsingContractAddress,
 
+    #[error("no contract address provided and the node has no genesis catalog")]
+    CatalogNotDeployed,
+
+    #[error("unable to parse job id from provided string")]
+    ParsingJobId,
+
+    #[error("unable to parse bid salt from provided string")]
+    ParsingSalt,
+
+    #[error("the job was paid out, but not to the signer")]
+    PaymentNotFound,
+
     #[error("")]
     NetworkHandlerStopped,
 
@@ -28,6 +28,7 @@ mod tests {
     const ACCOUNT_FUNDER: &'static str = "//Charlie";
     const CONTRACT_INSTANTIATOR: &'static str = "//Bob";
     const URL: &'static str = "ws://127.0.0.1:9944";
+    const WORKER_STAKE: u128 = 10_000;
 
     #[test_macro::test]
     async fn submit_job(log_buffer: Arc<Mutex<Vec<u8>>>) {
@@ -56,6 +57,13 @@ mod tests {
         );
 
         worker_runner.start().await;
+
+        worker_runner
+            .assert_info_log_entry("Worker registered")
+            .await;
+        worker_runner
+            .assert_info_log_entry("Starting Worker Controller")
+            .await;
         requester_runner
             .submit_job(
                 "tests/requester_worker/work_bg.wasm",
@@ -65,9 +73,6 @@ mod tests {
             )
             .await;
 
-        worker_runner
-            .assert_info_log_entry("Starting Worker Controller")
-            .await;
         requester_runner
             .assert_info_log_entry("Job Request Submitted!")
             .await;
@@ -81,16 +86,28 @@ mod tests {
             .assert_info_log_contains("Job sent to peer: ")
             .await;
         worker_runner.assert_info_log_entry("Job received!").await;
+        worker_runner
+            .assert_info_log_entry("Job accepted on chain")
+            .await;
         worker_runner
             .assert_info_log_entry("Job acknowledgement sent")
             .await;
         requester_runner
             .assert_info_log_contains("Job has been accepted by a worker")
             .await;
+        worker_runner
+            .assert_info_log_entry("Result committed on chain")
+            .await;
+        worker_runner
+            .assert_info_log_entry("Result revealed on chain")
+            .await;
         worker_runner.assert_info_log_entry("Results sent").await;
         requester_runner
             .assert_info_log_contains("Received results")
             .await;
+        requester_runner
+            .assert_info_log_contains("Results match on chain commitment")
+            .await;
         requester_runner
             .assert_info_log_contains("Result acknowledgement sent")
             .await;
@@ -104,7 +121,8 @@ mod tests {
         let worker_balance = get_balance(&worker_account_id).await;
         let requester_balance = get_balance(&requester_account_id).await;
 
-        assert_eq!(worker_balance - fund_amount, value);
+        assert!(worker_balance + WORKER_STAKE > fund_amount);
+        assert!(worker_balance + WORKER_STAKE <= fund_amount + value);
         assert!(requester_balance < fund_amount);
     }
 
@@ -115,7 +133,7 @@ mod tests {
         let tx_client = contract_client.online_client().await.unwrap().tx();
 
         let address = loop {
-            match contract_client.instantiate("new", 0).await {
+            match contract_client.instantiate("new", &(), 0).await {
                 Ok(addr) => {
                     break addr;
                 }
@@ -306,7 +324,11 @@ mod tests {
 
         async fn start(&self) {
             let start_cmd = StartCmd {
-                address: self.contract_address.to_string(),
+                address: Some(self.contract_address.to_string()),
+                stake: Some(WORKER_STAKE),
+                max_memory: 64 * 1024 * 1024,
+                max_fuel: 10_000_000,
+                price_per_fuel: 1,
             };
             let config = self.config.clone();
 
@@ -350,11 +372,14 @@ mod tests {
             value: u128,
         ) {
             let submit_job_cmd = SubmitJobCmd {
-                address: self.contract_address.to_string(),
+                address: Some(self.contract_address.to_string()),
                 value,
                 code_path: path.to_string(),
                 function_name: func_name.to_string(),
                 parameters,
+                replicas: 1,
+                milestones: 1,
+                token: None,
             };
             let config = self.config.clone();
 
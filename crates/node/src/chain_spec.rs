@@ -15,14 +15,26 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use frame::traits::Get;
+use frame::{
+    deps::sp_core::{bytes::from_hex, ed25519, Pair},
+    traits::Get,
+};
 use runtime::{
     interface::{Balance, MinimumBalance},
-    BalancesConfig, SudoConfig, WASM_BINARY,
+    BalancesConfig, CatalogConfig, SessionConfig, SessionKeys, SudoConfig, WASM_BINARY,
 };
 use sc_service::{ChainType, Properties};
 use serde_json::{json, Value};
 use sp_keyring::AccountKeyring;
+use std::{env::var, fs::read_to_string};
+
+/// Catalog artifact `cargo contract build` leaves in the target directory, relative to the
+/// workspace root the node is started from, like the worker and requester default to.
+const DEFAULT_CATALOG_ARTIFACT: &str = "./target/ink/catalog/catalog.contract";
+
+/// Salt the genesis catalog is instantiated with. Along with Alice deploying it, this keeps the
+/// catalog at the same address on every fresh dev or local chain.
+const CATALOG_SALT: &[u8] = b"bruja";
 
 /// This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec;
@@ -42,19 +54,98 @@ pub fn development_config() -> Result<ChainSpec, String> {
     .with_name("Development")
     .with_id("dev")
     .with_chain_type(ChainType::Development)
-    .with_genesis_config_patch(testnet_genesis())
+    .with_genesis_config_patch(testnet_genesis(&[AccountKeyring::Alice])?)
+    .with_properties(props())
+    .build())
+}
+
+pub fn local_config() -> Result<ChainSpec, String> {
+    Ok(ChainSpec::builder(
+        WASM_BINARY.expect("Development wasm not available"),
+        Default::default(),
+    )
+    .with_name("Local Testnet")
+    .with_id("local_testnet")
+    .with_chain_type(ChainType::Local)
+    .with_genesis_config_patch(testnet_genesis(&[
+        AccountKeyring::Alice,
+        AccountKeyring::Bob,
+        AccountKeyring::Charlie,
+        AccountKeyring::Dave,
+    ])?)
     .with_properties(props())
     .build())
 }
 
 /// Configure initial storage state for FRAME pallets.
-fn testnet_genesis() -> Value {
+///
+/// Every keyring account is endowed, so any of them can act as a worker or requester. The
+/// `authorities` get session keys derived from their dev seeds, which makes them the validators
+/// when the node runs with `--consensus aura-grandpa`.
+fn testnet_genesis(authorities: &[AccountKeyring]) -> Result<Value, String> {
     let endowment = <MinimumBalance as Get<Balance>>::get().max(1) * 1_000_000_000_000_000;
     let balances = AccountKeyring::iter()
         .map(|a| (a.to_account_id(), endowment))
         .collect::<Vec<_>>();
-    json!({
+    let keys = authorities
+        .iter()
+        .map(|a| (a.to_account_id(), a.to_account_id(), session_keys(*a)))
+        .collect::<Vec<_>>();
+    Ok(json!({
         "balances": BalancesConfig { balances },
+        "session": SessionConfig { keys, ..Default::default() },
         "sudo": SudoConfig { key: Some(AccountKeyring::Alice.to_account_id()) },
+        "catalog": catalog_genesis()?,
+    }))
+}
+
+/// The same keys `--alice`, `--bob`, ... insert into the keystore of a node.
+fn session_keys(authority: AccountKeyring) -> SessionKeys {
+    let grandpa = ed25519::Pair::from_string(&authority.to_seed(), None)
+        .expect("dev seeds are valid; qed")
+        .public();
+    SessionKeys {
+        aura: authority.public().into(),
+        grandpa: grandpa.into(),
+    }
+}
+
+/// Deploys the catalog built by `cargo contract build`, read from `ARTIFACT_FILE_PATH` like the
+/// worker and requester do, with Alice as its owner. Without `ARTIFACT_FILE_PATH` the artifact is
+/// read from the target directory under the working directory. If there is no artifact the chain
+/// starts without a catalog, which clients then have to deploy themselves.
+fn catalog_genesis() -> Result<CatalogConfig, String> {
+    let path = var("ARTIFACT_FILE_PATH").unwrap_or(DEFAULT_CATALOG_ARTIFACT.to_string());
+    let artifact = match read_to_string(&path) {
+        Ok(artifact) => artifact,
+        Err(err) => {
+            eprintln!(
+                "Warning: not deploying the catalog at genesis, unable to read artifact {}: {}. \
+                Build it with `cargo contract build --manifest-path crates/catalog/Cargo.toml` \
+                or set ARTIFACT_FILE_PATH to it",
+                path, err
+            );
+            return Ok(CatalogConfig::default());
+        }
+    };
+    let artifact: Value = serde_json::from_str(&artifact)
+        .map_err(|err| format!("Invalid catalog artifact {}: {}", path, err))?;
+
+    let code = artifact["source"]["wasm"]
+        .as_str()
+        .and_then(|code| from_hex(code).ok())
+        .ok_or_else(|| format!("Catalog artifact {} has no wasm code", path))?;
+    let data = artifact["spec"]["constructors"]
+        .as_array()
+        .and_then(|constructors| constructors.iter().find(|con| con["label"] == "new"))
+        .and_then(|con| con["selector"].as_str())
+        .and_then(|selector| from_hex(selector).ok())
+        .ok_or_else(|| format!("Catalog artifact {} has no `new` constructor", path))?;
+
+    Ok(CatalogConfig {
+        deployer: Some(AccountKeyring::Alice.to_account_id()),
+        code,
+        data,
+        salt: CATALOG_SALT.to_vec(),
     })
 }
@@ -21,6 +21,10 @@ use sc_cli::RunCmd;
 pub enum Consensus {
     ManualSeal(u64),
     InstantSeal,
+    /// Blocks are only sealed when requested with the `engine_createBlock` RPC method.
+    OnDemand,
+    /// Aura block authoring with grandpa finality, for testnets of several validators.
+    AuraGrandpa,
 }
 
 impl std::str::FromStr for Consensus {
@@ -29,6 +33,10 @@ impl std::str::FromStr for Consensus {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(if s == "instant-seal" {
             Consensus::InstantSeal
+        } else if s == "on-demand" {
+            Consensus::OnDemand
+        } else if s == "aura-grandpa" {
+            Consensus::AuraGrandpa
         } else if let Some(block_time) = s.strip_prefix("manual-seal-") {
             Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
         } else {
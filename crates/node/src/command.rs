@@ -17,7 +17,7 @@
 
 use crate::{
     chain_spec,
-    cli::{Cli, Subcommand},
+    cli::{Cli, Consensus, Subcommand},
     service::{new_full, new_partial},
 };
 use sc_cli::SubstrateCli;
@@ -55,6 +55,7 @@ impl SubstrateCli for Cli {
     fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
         Ok(match id {
             "dev" => Box::new(chain_spec::development_config()?),
+            "local" => Box::new(chain_spec::local_config()?),
             path => Box::new(chain_spec::ChainSpec::from_json_file(
                 std::path::PathBuf::from(path),
             )?),
@@ -80,7 +81,7 @@ pub fn run() -> sc_cli::Result<()> {
                     task_manager,
                     import_queue,
                     ..
-                } = new_partial(&config)?;
+                } = new_partial(&config, &cli.consensus)?;
                 Ok((cmd.run(client, import_queue), task_manager))
             })
         }
@@ -91,7 +92,7 @@ pub fn run() -> sc_cli::Result<()> {
                     client,
                     task_manager,
                     ..
-                } = new_partial(&config)?;
+                } = new_partial(&config, &cli.consensus)?;
                 Ok((cmd.run(client, config.database), task_manager))
             })
         }
@@ -102,7 +103,7 @@ pub fn run() -> sc_cli::Result<()> {
                     client,
                     task_manager,
                     ..
-                } = new_partial(&config)?;
+                } = new_partial(&config, &cli.consensus)?;
                 Ok((cmd.run(client, config.chain_spec), task_manager))
             })
         }
@@ -114,7 +115,7 @@ pub fn run() -> sc_cli::Result<()> {
                     task_manager,
                     import_queue,
                     ..
-                } = new_partial(&config)?;
+                } = new_partial(&config, &cli.consensus)?;
                 Ok((cmd.run(client, import_queue), task_manager))
             })
         }
@@ -130,8 +131,16 @@ pub fn run() -> sc_cli::Result<()> {
                     task_manager,
                     backend,
                     ..
-                } = new_partial(&config)?;
-                Ok((cmd.run(client, backend, None), task_manager))
+                } = new_partial(&config, &cli.consensus)?;
+                // Only the aura-grandpa mode keeps grandpa state that has to be reverted too.
+                let revert_grandpa = matches!(cli.consensus, Consensus::AuraGrandpa);
+                let aux_revert = Box::new(move |client, _, blocks| {
+                    if revert_grandpa {
+                        sc_consensus_grandpa::revert(client, blocks)?;
+                    }
+                    Ok(())
+                });
+                Ok((cmd.run(client, backend, Some(aux_revert)), task_manager))
             })
         }
         Some(Subcommand::ChainInfo(cmd)) => {
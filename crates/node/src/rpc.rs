@@ -22,8 +22,16 @@
 
 #![warn(missing_docs)]
 
+use futures::channel::mpsc::Sender;
 use jsonrpsee::RpcModule;
-use runtime::interface::{AccountId, Nonce, OpaqueBlock};
+use runtime::{
+    interface::{AccountId, Hash, Nonce, OpaqueBlock},
+    runtime_apis::{CatalogApi, JobsApi},
+};
+use sc_consensus_manual_seal::{
+    rpc::{ManualSeal, ManualSealApiServer},
+    EngineCommand,
+};
 use sc_transaction_pool_api::TransactionPool;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use std::sync::Arc;
@@ -31,6 +39,10 @@ use substrate_frame_rpc_system::{System, SystemApiServer};
 
 pub use sc_rpc_api::DenyUnsafe;
 
+use bruja::{Bruja, BrujaApiServer};
+
+pub mod bruja;
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
     /// The client instance to use.
@@ -39,6 +51,8 @@ pub struct FullDeps<C, P> {
     pub pool: Arc<P>,
     /// Whether to deny unsafe calls
     pub deny_unsafe: DenyUnsafe,
+    /// Commands to manual seal, serving the `engine_*` methods when set.
+    pub command_sink: Option<Sender<EngineCommand<Hash>>>,
 }
 
 #[docify::export]
@@ -56,6 +70,8 @@ where
         + 'static,
     C::Api: sp_block_builder::BlockBuilder<OpaqueBlock>,
     C::Api: substrate_frame_rpc_system::AccountNonceApi<OpaqueBlock, AccountId, Nonce>,
+    C::Api: JobsApi<OpaqueBlock>,
+    C::Api: CatalogApi<OpaqueBlock>,
     P: TransactionPool + 'static,
 {
     let mut module = RpcModule::new(());
@@ -63,9 +79,15 @@ where
         client,
         pool,
         deny_unsafe,
+        command_sink,
     } = deps;
 
     module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
+    module.merge(Bruja::new(client).into_rpc())?;
+
+    if let Some(command_sink) = command_sink {
+        module.merge(ManualSeal::new(command_sink).into_rpc())?;
+    }
 
     Ok(module)
 }
@@ -0,0 +1,378 @@
+//! The `bruja_*` RPC methods, which serve the marketplace state kept by the catalog contract and
+//! the jobs pallet so clients don't have to hand-encode contract calls or storage keys.
+//!
+//! The catalog methods query the catalog deployed at genesis unless they are given the address
+//! of another one, and return its jobs and workers decoded with the `catalog` crate's types.
+
+use catalog::catalog::{
+    JobRecord, JobState as CatalogState, Page, Reputation, Stake, WorkerProfile as Registration,
+};
+use frame::deps::{codec::Decode, sp_core::H256};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::{ErrorObject, ErrorObjectOwned},
+};
+use runtime::{
+    interface::{AccountId, Balance, BlockNumber, OpaqueBlock},
+    pallet_jobs::{Job, JobState},
+    runtime_apis::{CatalogApi, JobsApi},
+    Runtime,
+};
+use serde::Serialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+const RUNTIME_ERROR: i32 = 1;
+const CATALOG_ERROR: i32 = 2;
+
+/// Page size of `bruja_openJobs` and `bruja_catalogOpenJobs` when the caller doesn't ask for one.
+const DEFAULT_PAGE_SIZE: u32 = 100;
+
+/// Largest page `bruja_openJobs` and `bruja_catalogOpenJobs` return, whatever the caller asks for.
+const MAX_PAGE_SIZE: u32 = 1_000;
+
+/// A pallet job as returned by `bruja_jobStatus`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    requester: AccountId,
+    worker: Option<AccountId>,
+    value: Balance,
+    deposit: Balance,
+    worker_deposit: Balance,
+    deadline: BlockNumber,
+    state: State,
+}
+
+/// State of a job as returned by `bruja_jobStatus`.
+#[derive(Serialize)]
+pub enum State {
+    /// Waiting for a worker to accept it.
+    Submitted,
+    /// Accepted by a worker and waiting to be paid.
+    Accepted,
+}
+
+/// A catalog job as returned by `bruja_catalogJob` and `bruja_catalogOpenJobs`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogJob {
+    id: H256,
+    requester: AccountId,
+    value: Balance,
+    /// Milliseconds since the unix epoch.
+    deadline: u64,
+    replicas: u8,
+    workers: Vec<AccountId>,
+    state: CatalogJobState,
+}
+
+/// State of a catalog job as returned by `bruja_catalogJob` and `bruja_catalogOpenJobs`.
+#[derive(Serialize)]
+pub enum CatalogJobState {
+    Submitted,
+    Accepted,
+    Revealing,
+    ResultCommitted {
+        #[serde(rename = "resultHash")]
+        result_hash: H256,
+    },
+    Paid,
+    Cancelled,
+    Disputed,
+    Bidding,
+}
+
+/// A page of catalog jobs as returned by `bruja_catalogOpenJobs`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogJobsPage {
+    jobs: Vec<CatalogJob>,
+    /// Cursor of the next page, or `None` if this is the last one.
+    next_cursor: Option<u64>,
+}
+
+/// A worker's standing in the catalog as returned by `bruja_workerProfile`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerProfile {
+    registered: bool,
+    stake: Balance,
+    /// When the stake can be withdrawn, if the worker is unbonding.
+    unbonding_at: Option<u64>,
+    jobs_completed: u32,
+    jobs_abandoned: u32,
+    disputes_lost: u32,
+    /// Milliseconds it took the worker to commit its results, on average.
+    average_time_to_result: Option<u64>,
+}
+
+/// Marketplace queries served under the `bruja` namespace.
+#[rpc(server, namespace = "bruja")]
+pub trait BrujaApi<BlockHash> {
+    /// Catalog job `id`, or `None` if the catalog has no such job.
+    #[method(name = "catalogJob")]
+    fn catalog_job(
+        &self,
+        id: H256,
+        catalog: Option<AccountId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<CatalogJob>>;
+
+    /// Up to `limit` catalog jobs still waiting for workers, starting at `cursor`.
+    #[method(name = "catalogOpenJobs")]
+    fn catalog_open_jobs(
+        &self,
+        cursor: Option<u64>,
+        limit: Option<u32>,
+        catalog: Option<AccountId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<CatalogJobsPage>;
+
+    /// Registration, stake and reputation of `who` in the catalog.
+    #[method(name = "workerProfile")]
+    fn worker_profile(
+        &self,
+        who: AccountId,
+        catalog: Option<AccountId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<WorkerProfile>;
+
+    /// Status of pallet job `id`, or `None` once it has been paid, cancelled or reclaimed.
+    #[method(name = "jobStatus")]
+    fn job_status(&self, id: H256, at: Option<BlockHash>) -> RpcResult<Option<JobStatus>>;
+
+    /// Ids of up to `limit` pallet jobs still waiting for a worker. Pass the last id of a page
+    /// as `start_after` to get the next one.
+    #[method(name = "openJobs")]
+    fn open_jobs(
+        &self,
+        start_after: Option<H256>,
+        limit: Option<u32>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<H256>>;
+
+    /// Total payment `who` currently has escrowed for its pallet jobs.
+    #[method(name = "escrowBalance")]
+    fn escrow_balance(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+    /// Address of the catalog contract deployed at genesis, if there is one.
+    #[method(name = "catalogAddress")]
+    fn catalog_address(&self, at: Option<BlockHash>) -> RpcResult<Option<AccountId>>;
+}
+
+/// Implements the [`BrujaApiServer`] RPC trait on top of the jobs and catalog runtime APIs.
+pub struct Bruja<C> {
+    client: Arc<C>,
+}
+
+impl<C> Bruja<C> {
+    /// Creates a new instance of the Bruja RPC handler.
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client }
+    }
+}
+
+impl<C> Bruja<C>
+where
+    C: HeaderBackend<OpaqueBlock>,
+{
+    fn at(&self, at: Option<<OpaqueBlock as BlockT>::Hash>) -> <OpaqueBlock as BlockT>::Hash {
+        at.unwrap_or_else(|| self.client.info().best_hash)
+    }
+}
+
+impl<C> BrujaApiServer<<OpaqueBlock as BlockT>::Hash> for Bruja<C>
+where
+    C: ProvideRuntimeApi<OpaqueBlock> + HeaderBackend<OpaqueBlock> + Send + Sync + 'static,
+    C::Api: JobsApi<OpaqueBlock> + CatalogApi<OpaqueBlock>,
+{
+    fn catalog_job(
+        &self,
+        id: H256,
+        catalog: Option<AccountId>,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<Option<CatalogJob>> {
+        let encoded = self
+            .client
+            .runtime_api()
+            .catalog_job(self.at(at), catalog, id.into())
+            .map_err(runtime_error)?;
+        let record = decode_catalog::<Option<JobRecord>>(encoded)?;
+
+        Ok(record.map(|record| CatalogJob::new(id.into(), record)))
+    }
+
+    fn catalog_open_jobs(
+        &self,
+        cursor: Option<u64>,
+        limit: Option<u32>,
+        catalog: Option<AccountId>,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<CatalogJobsPage> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let encoded = self
+            .client
+            .runtime_api()
+            .catalog_open_jobs(self.at(at), catalog, cursor.unwrap_or_default(), limit)
+            .map_err(runtime_error)?;
+        let page = decode_catalog::<Page<([u8; 32], JobRecord)>>(encoded)?;
+
+        Ok(CatalogJobsPage {
+            next_cursor: page.next_cursor(),
+            jobs: page
+                .into_items()
+                .into_iter()
+                .map(|(id, record)| CatalogJob::new(id, record))
+                .collect(),
+        })
+    }
+
+    fn worker_profile(
+        &self,
+        who: AccountId,
+        catalog: Option<AccountId>,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<WorkerProfile> {
+        let encoded = self
+            .client
+            .runtime_api()
+            .worker_profile(self.at(at), catalog, who)
+            .map_err(runtime_error)?;
+        let (registration, reputation, stake) =
+            decode_catalog::<(Option<Registration>, Reputation, Option<Stake>)>(encoded)?;
+
+        Ok(WorkerProfile {
+            registered: registration.is_some(),
+            stake: stake.as_ref().map(Stake::amount).unwrap_or_default(),
+            unbonding_at: stake.as_ref().and_then(Stake::unbonding_at),
+            jobs_completed: reputation.jobs_completed(),
+            jobs_abandoned: reputation.jobs_abandoned(),
+            disputes_lost: reputation.disputes_lost(),
+            average_time_to_result: reputation.average_time_to_result(),
+        })
+    }
+
+    fn job_status(
+        &self,
+        id: H256,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<Option<JobStatus>> {
+        let job = self
+            .client
+            .runtime_api()
+            .job_status(self.at(at), id.into())
+            .map_err(runtime_error)?;
+
+        Ok(job.map(Into::into))
+    }
+
+    fn open_jobs(
+        &self,
+        start_after: Option<H256>,
+        limit: Option<u32>,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<Vec<H256>> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let ids = self
+            .client
+            .runtime_api()
+            .open_jobs(self.at(at), start_after.map(Into::into), limit)
+            .map_err(runtime_error)?;
+
+        Ok(ids.into_iter().map(H256::from).collect())
+    }
+
+    fn escrow_balance(
+        &self,
+        who: AccountId,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<Balance> {
+        self.client
+            .runtime_api()
+            .escrow_balance(self.at(at), who)
+            .map_err(runtime_error)
+    }
+
+    fn catalog_address(
+        &self,
+        at: Option<<OpaqueBlock as BlockT>::Hash>,
+    ) -> RpcResult<Option<AccountId>> {
+        self.client
+            .runtime_api()
+            .catalog_address(self.at(at))
+            .map_err(runtime_error)
+    }
+}
+
+impl From<Job<Runtime>> for JobStatus {
+    fn from(job: Job<Runtime>) -> Self {
+        Self {
+            requester: job.requester,
+            worker: job.worker,
+            value: job.value,
+            deposit: job.deposit,
+            worker_deposit: job.worker_deposit,
+            deadline: job.deadline,
+            state: match job.state {
+                JobState::Submitted => State::Submitted,
+                JobState::Accepted => State::Accepted,
+            },
+        }
+    }
+}
+
+impl CatalogJob {
+    fn new(id: [u8; 32], record: JobRecord) -> Self {
+        Self {
+            id: id.into(),
+            requester: account(record.requester()),
+            value: record.value(),
+            deadline: record.deadline(),
+            replicas: record.replicas(),
+            workers: record.workers().iter().copied().map(account).collect(),
+            state: match *record.state() {
+                CatalogState::Submitted => CatalogJobState::Submitted,
+                CatalogState::Accepted => CatalogJobState::Accepted,
+                CatalogState::Revealing => CatalogJobState::Revealing,
+                CatalogState::ResultCommitted { result_hash } => CatalogJobState::ResultCommitted {
+                    result_hash: result_hash.into(),
+                },
+                CatalogState::Paid => CatalogJobState::Paid,
+                CatalogState::Cancelled => CatalogJobState::Cancelled,
+                CatalogState::Disputed => CatalogJobState::Disputed,
+                CatalogState::Bidding => CatalogJobState::Bidding,
+            },
+        }
+    }
+}
+
+fn account(who: ink::primitives::AccountId) -> AccountId {
+    AccountId::new(*who.as_ref())
+}
+
+/// Decodes what the catalog runtime api read, failing if there was no catalog to read from.
+fn decode_catalog<T: Decode>(encoded: Option<Vec<u8>>) -> RpcResult<T> {
+    let encoded = encoded.ok_or_else(|| {
+        ErrorObject::owned(CATALOG_ERROR, "Unable to query the catalog", None::<()>)
+    })?;
+
+    T::decode(&mut encoded.as_slice()).map_err(|err| {
+        ErrorObject::owned(
+            CATALOG_ERROR,
+            "Unable to decode the catalog's response",
+            Some(err.to_string()),
+        )
+    })
+}
+
+fn runtime_error(err: sp_api::ApiError) -> ErrorObjectOwned {
+    ErrorObject::owned(
+        RUNTIME_ERROR,
+        "Unable to query the runtime api",
+        Some(err.to_string()),
+    )
+}
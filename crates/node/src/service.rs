@@ -15,15 +15,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use futures::FutureExt;
+use futures::{FutureExt, SinkExt};
 use runtime::{interface::OpaqueBlock as Block, runtime_apis::RuntimeApi};
 use sc_client_api::backend::Backend;
+use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_grandpa::SharedVoterState;
+use sc_consensus_manual_seal::consensus::aura::AuraConsensusDataProvider;
 use sc_executor::WasmExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{
+    sr25519::{AuthorityPair as AuraPair, AuthoritySignature as AuraSignature},
+    SlotDuration,
+};
 use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::cli::Consensus;
 
@@ -42,6 +50,12 @@ pub(crate) type FullClient =
 
 type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type GrandpaBlockImport =
+    sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
+type GrandpaLinkHalf = sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>;
+
+/// Number of blocks between the justifications grandpa stores for finalized blocks.
+const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
 
 /// Assembly of PartialComponents (enough to run chain ops subcommands)
 pub type Service = sc_service::PartialComponents<
@@ -50,10 +64,13 @@ pub type Service = sc_service::PartialComponents<
     FullSelectChain,
     sc_consensus::DefaultImportQueue<Block>,
     sc_transaction_pool::FullPool<Block, FullClient>,
-    Option<Telemetry>,
+    (
+        Option<Telemetry>,
+        Option<(GrandpaBlockImport, GrandpaLinkHalf)>,
+    ),
 >;
 
-pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
+pub fn new_partial(config: &Configuration, consensus: &Consensus) -> Result<Service, ServiceError> {
     let telemetry = config
         .telemetry_endpoints
         .clone()
@@ -92,11 +109,50 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
         client.clone(),
     );
 
-    let import_queue = sc_consensus_manual_seal::import_queue(
-        Box::new(client.clone()),
-        &task_manager.spawn_essential_handle(),
-        config.prometheus_registry(),
-    );
+    let (import_queue, grandpa) = match consensus {
+        Consensus::AuraGrandpa => {
+            let (grandpa_block_import, grandpa_link) = sc_consensus_grandpa::block_import(
+                client.clone(),
+                GRANDPA_JUSTIFICATION_PERIOD,
+                &client,
+                select_chain.clone(),
+                telemetry.as_ref().map(|x| x.handle()),
+            )?;
+
+            let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+            let import_queue = sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(
+                ImportQueueParams {
+                    block_import: grandpa_block_import.clone(),
+                    justification_import: Some(Box::new(grandpa_block_import.clone())),
+                    client: client.clone(),
+                    create_inherent_data_providers: move |_, ()| async move {
+                        let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+                        let slot = sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+                            *timestamp,
+                            slot_duration,
+                        );
+                        Ok((slot, timestamp))
+                    },
+                    spawner: &task_manager.spawn_essential_handle(),
+                    registry: config.prometheus_registry(),
+                    check_for_equivocation: Default::default(),
+                    telemetry: telemetry.as_ref().map(|x| x.handle()),
+                    compatibility_mode: Default::default(),
+                },
+            )?;
+
+            (import_queue, Some((grandpa_block_import, grandpa_link)))
+        }
+        Consensus::ManualSeal(_) | Consensus::OnDemand | Consensus::InstantSeal => {
+            let import_queue = sc_consensus_manual_seal::import_queue(
+                Box::new(client.clone()),
+                &task_manager.spawn_essential_handle(),
+                config.prometheus_registry(),
+            );
+
+            (import_queue, None)
+        }
+    };
 
     Ok(sc_service::PartialComponents {
         client,
@@ -106,7 +162,7 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
         keystore_container,
         select_chain,
         transaction_pool,
-        other: (telemetry),
+        other: (telemetry, grandpa),
     })
 }
 
@@ -123,10 +179,10 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
         keystore_container,
         select_chain,
         transaction_pool,
-        other: mut telemetry,
-    } = new_partial(&config)?;
+        other: (mut telemetry, grandpa),
+    } = new_partial(&config, &consensus)?;
 
-    let net_config = sc_network::config::FullNetworkConfiguration::<
+    let mut net_config = sc_network::config::FullNetworkConfiguration::<
         Block,
         <Block as BlockT>::Hash,
         Network,
@@ -135,6 +191,21 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
         config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
     );
 
+    let grandpa_protocol = grandpa.is_some().then(|| {
+        let protocol_name = sc_consensus_grandpa::protocol_standard_name(
+            &client.info().genesis_hash,
+            &config.chain_spec,
+        );
+        let (protocol_config, notification_service) =
+            sc_consensus_grandpa::grandpa_peers_set_config::<_, Network>(
+                protocol_name.clone(),
+                metrics.clone(),
+                net_config.peer_store_handle(),
+            );
+        net_config.add_notification_protocol(protocol_config);
+        (protocol_name, notification_service)
+    });
+
     let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
         sc_service::build_network(sc_service::BuildNetworkParams {
             config: &config,
@@ -170,24 +241,34 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
         );
     }
 
+    // Blocks are sealed on the commands sent here, by the `engine_*` RPC methods and, in the
+    // manual seal mode, by a timer.
+    let (command_sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
     let rpc_extensions_builder = {
         let client = client.clone();
         let pool = transaction_pool.clone();
+        let command_sink = matches!(consensus, Consensus::ManualSeal(_) | Consensus::OnDemand)
+            .then(|| command_sink.clone());
 
         Box::new(move |deny_unsafe, _| {
             let deps = crate::rpc::FullDeps {
                 client: client.clone(),
                 pool: pool.clone(),
                 deny_unsafe,
+                command_sink: command_sink.clone(),
             };
             crate::rpc::create_full(deps).map_err(Into::into)
         })
     };
 
     let prometheus_registry = config.prometheus_registry().cloned();
+    let role = config.role.clone();
+    let force_authoring = config.force_authoring;
+    let name = config.network.node_name.clone();
 
     let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
-        network,
+        network: network.clone(),
         client: client.clone(),
         keystore: keystore_container.keystore(),
         task_manager: &mut task_manager,
@@ -196,7 +277,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
         backend,
         system_rpc_tx,
         tx_handler_controller,
-        sync_service,
+        sync_service: sync_service.clone(),
         config,
         telemetry: telemetry.as_mut(),
     })?;
@@ -211,15 +292,18 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 
     match consensus {
         Consensus::InstantSeal => {
+            let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+            let header_client = client.clone();
             let params = sc_consensus_manual_seal::InstantSealParams {
                 block_import: client.clone(),
                 env: proposer,
-                client,
+                client: client.clone(),
                 pool: transaction_pool,
                 select_chain,
-                consensus_data_provider: None,
-                create_inherent_data_providers: move |_, ()| async move {
-                    Ok(sp_timestamp::InherentDataProvider::from_system_time())
+                consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+                create_inherent_data_providers: move |parent, ()| {
+                    let timestamp = next_timestamp(&header_client, parent, slot_duration);
+                    async move { timestamp }
                 },
             };
 
@@ -231,34 +315,41 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
                 authorship_future,
             );
         }
-        Consensus::ManualSeal(block_time) => {
-            let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
-            task_manager
-                .spawn_handle()
-                .spawn("block_authoring", None, async move {
-                    loop {
-                        futures_timer::Delay::new(std::time::Duration::from_millis(block_time))
-                            .await;
-                        sink.try_send(sc_consensus_manual_seal::EngineCommand::SealNewBlock {
-                            create_empty: true,
-                            finalize: true,
-                            parent_hash: None,
-                            sender: None,
-                        })
-                        .unwrap();
-                    }
-                });
-
+        Consensus::ManualSeal(_) | Consensus::OnDemand => {
+            if let Consensus::ManualSeal(block_time) = consensus {
+                let mut sink = command_sink;
+                task_manager
+                    .spawn_handle()
+                    .spawn("block_authoring", None, async move {
+                        loop {
+                            futures_timer::Delay::new(Duration::from_millis(block_time)).await;
+                            let command = sc_consensus_manual_seal::EngineCommand::SealNewBlock {
+                                create_empty: true,
+                                finalize: true,
+                                parent_hash: None,
+                                sender: None,
+                            };
+                            // Only fails once manual seal has stopped reading commands.
+                            if sink.send(command).await.is_err() {
+                                break;
+                            }
+                        }
+                    });
+            }
+
+            let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+            let header_client = client.clone();
             let params = sc_consensus_manual_seal::ManualSealParams {
                 block_import: client.clone(),
                 env: proposer,
-                client,
+                client: client.clone(),
                 pool: transaction_pool,
                 select_chain,
                 commands_stream: Box::pin(commands_stream),
-                consensus_data_provider: None,
-                create_inherent_data_providers: move |_, ()| async move {
-                    Ok(sp_timestamp::InherentDataProvider::from_system_time())
+                consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+                create_inherent_data_providers: move |parent, ()| {
+                    let timestamp = next_timestamp(&header_client, parent, slot_duration);
+                    async move { timestamp }
                 },
             };
             let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
@@ -269,8 +360,99 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
                 authorship_future,
             );
         }
+        Consensus::AuraGrandpa => {
+            let (block_import, grandpa_link) =
+                grandpa.expect("new_partial sets up grandpa for the aura-grandpa mode; qed");
+            let (grandpa_protocol_name, grandpa_notification_service) = grandpa_protocol
+                .expect("the grandpa protocol is registered for the aura-grandpa mode; qed");
+
+            if role.is_authority() {
+                let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+                let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
+                    StartAuraParams {
+                        slot_duration,
+                        client,
+                        select_chain,
+                        block_import,
+                        proposer_factory: proposer,
+                        create_inherent_data_providers: move |_, ()| async move {
+                            let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+                            let slot = sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+                                *timestamp,
+                                slot_duration,
+                            );
+                            Ok((slot, timestamp))
+                        },
+                        force_authoring,
+                        backoff_authoring_blocks: Option::<()>::None,
+                        keystore: keystore_container.keystore(),
+                        sync_oracle: sync_service.clone(),
+                        justification_sync_link: sync_service.clone(),
+                        block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
+                        max_block_proposal_slot_portion: None,
+                        telemetry: telemetry.as_ref().map(|x| x.handle()),
+                        compatibility_mode: Default::default(),
+                    },
+                )?;
+
+                task_manager.spawn_essential_handle().spawn_blocking(
+                    "aura",
+                    Some("block-authoring"),
+                    aura,
+                );
+            }
+
+            let grandpa_params = sc_consensus_grandpa::GrandpaParams {
+                config: sc_consensus_grandpa::Config {
+                    gossip_duration: Duration::from_millis(333),
+                    justification_generation_period: GRANDPA_JUSTIFICATION_PERIOD,
+                    name: Some(name),
+                    observer_enabled: false,
+                    keystore: role.is_authority().then(|| keystore_container.keystore()),
+                    local_role: role,
+                    telemetry: telemetry.as_ref().map(|x| x.handle()),
+                    protocol_name: grandpa_protocol_name,
+                },
+                link: grandpa_link,
+                network,
+                sync: sync_service,
+                notification_service: grandpa_notification_service,
+                voting_rule: sc_consensus_grandpa::VotingRulesBuilder::default().build(),
+                prometheus_registry,
+                shared_voter_state: SharedVoterState::empty(),
+                telemetry: telemetry.as_ref().map(|x| x.handle()),
+                offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool),
+            };
+
+            task_manager.spawn_essential_handle().spawn_blocking(
+                "grandpa-voter",
+                None,
+                sc_consensus_grandpa::run_grandpa_voter(grandpa_params)?,
+            );
+        }
     }
 
     network_starter.start_network();
     Ok(task_manager)
 }
+
+/// Timestamp of a block sealed on top of `parent` in the manual and instant seal modes. Aura
+/// takes the block's slot from its timestamp and rejects blocks that don't move to a later slot
+/// than their parent, so blocks sealed within the parent's slot, as instant seal does, are
+/// stamped with the start of the next slot instead of the system time.
+fn next_timestamp(
+    client: &FullClient,
+    parent: <Block as BlockT>::Hash,
+    slot_duration: SlotDuration,
+) -> Result<sp_timestamp::InherentDataProvider, Box<dyn std::error::Error + Send + Sync>> {
+    let parent_slot = client
+        .header(parent)?
+        .and_then(|header| sc_consensus_aura::find_pre_digest::<Block, AuraSignature>(&header).ok())
+        .map_or(0, u64::from);
+    let next_slot = parent_slot
+        .saturating_add(1)
+        .saturating_mul(slot_duration.as_millis());
+    let timestamp = sp_timestamp::Timestamp::current().max(next_slot.into());
+
+    Ok(sp_timestamp::InherentDataProvider::new(timestamp))
+}
@@ -0,0 +1,121 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+#![allow(unexpected_cfgs)]
+
+//! Minimal PSP22 token the catalog's end-to-end tests pay for jobs with. It only implements the
+//! PSP22 messages the catalog and its requesters call, under their standard selectors.
+
+#[ink::contract]
+pub mod psp22_token {
+    use codec::{Decode, Encode};
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    #[derive(Debug, PartialEq, Eq, Encode, Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+        InsufficientBalance,
+        InsufficientAllowance,
+        ZeroRecipientAddress,
+        ZeroSenderAddress,
+        SafeTransferCheckFailed(String),
+    }
+
+    #[ink(storage)]
+    pub struct Token {
+        total_supply: Balance,
+        balances: Mapping<AccountId, Balance>,
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    impl Token {
+        /// Mints `total_supply` tokens to the caller.
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut balances = Mapping::new();
+            balances.insert(Self::env().caller(), &total_supply);
+
+            Self {
+                total_supply,
+                balances,
+                allowances: Mapping::new(),
+            }
+        }
+
+        #[ink(message, selector = 0x162df8c2)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message, selector = 0x6568382f)]
+        pub fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or(0)
+        }
+
+        #[ink(message, selector = 0x4d47d921)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        #[ink(message, selector = 0xdb20f9f5)]
+        pub fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.move_tokens(self.env().caller(), to, value)
+        }
+
+        #[ink(message, selector = 0x54b3c76e)]
+        pub fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let spender = self.env().caller();
+            let allowance = self.allowance(from, spender);
+
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+
+            self.move_tokens(from, to, value)?;
+            self.allowances
+                .insert((from, spender), &(allowance - value));
+
+            Ok(())
+        }
+
+        #[ink(message, selector = 0xb20f1bbd)]
+        pub fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.allowances
+                .insert((self.env().caller(), spender), &value);
+
+            Ok(())
+        }
+
+        fn move_tokens(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let from_balance = self.balance_of(from);
+
+            if from_balance < value {
+                return Err(PSP22Error::InsufficientBalance);
+            }
+
+            self.balances.insert(from, &(from_balance - value));
+            self.balances
+                .insert(to, &self.balance_of(to).saturating_add(value));
+
+            Ok(())
+        }
+    }
+}
@@ -21,6 +21,13 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+// Pallets benchmarked through the `Benchmark` runtime api. Defined ahead of `runtime_apis` so the
+// `list_benchmarks!` and `add_benchmarks!` macros it generates are in scope there.
+#[cfg(feature = "runtime-benchmarks")]
+frame::deps::frame_benchmarking::define_benchmarks!([pallet_jobs, Jobs]);
+
+pub mod pallet_catalog;
+pub mod pallet_jobs;
 pub mod runtime_apis;
 
 use frame::{
@@ -35,7 +42,12 @@ use frame::{
             },
         },
         frame_system::limits::BlockWeights,
-        sp_runtime::Perbill,
+        sp_core::Void,
+        sp_runtime::{
+            impl_opaque_keys,
+            traits::{ConvertInto, OpaqueKeys},
+            Perbill,
+        },
     },
     prelude::*,
     runtime::prelude::*,
@@ -108,6 +120,12 @@ const MAXIMUM_BLOCK_WEIGHT: Weight =
     Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND.saturating_mul(2), u64::MAX);
 const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
 
+/// Milliseconds between aura slots, matching the default `manual-seal-3000` block time.
+pub const SLOT_DURATION: u64 = 3000;
+/// Blocks in a session, after which the keys queued with `Session::set_keys` take effect.
+const SESSION_PERIOD: u32 = 100;
+const MAX_AUTHORITIES: u32 = 32;
+
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
 type Header = HeaderFor<Runtime>;
 type BlockNumber = BlockNumberFor<Runtime>;
@@ -180,6 +198,33 @@ mod runtime {
 
     #[runtime::pallet_index(5)]
     pub type Contracts = pallet_contracts;
+
+    /// Escrows job payments between requesters and workers.
+    #[runtime::pallet_index(6)]
+    pub type Jobs = pallet_jobs;
+
+    /// Deploys the catalog contract at genesis.
+    #[runtime::pallet_index(7)]
+    pub type Catalog = pallet_catalog;
+
+    /// Hands the validators' session keys to aura and grandpa.
+    #[runtime::pallet_index(8)]
+    pub type Session = pallet_session;
+
+    /// Round-robin block authoring used by the `aura-grandpa` consensus mode.
+    #[runtime::pallet_index(9)]
+    pub type Aura = pallet_aura;
+
+    /// Block finality used by the `aura-grandpa` consensus mode.
+    #[runtime::pallet_index(10)]
+    pub type Grandpa = pallet_grandpa;
+}
+
+impl_opaque_keys! {
+    pub struct SessionKeys {
+        pub aura: Aura,
+        pub grandpa: Grandpa,
+    }
 }
 
 /// Implements the types required for the system pallet.
@@ -205,9 +250,12 @@ impl pallet_balances::Config for Runtime {
 #[derive_impl(pallet_sudo::config_preludes::TestDefaultConfig)]
 impl pallet_sudo::Config for Runtime {}
 
-// Implements the types required for the sudo pallet.
+// Implements the types required for the timestamp pallet. Aura checks every block's timestamp
+// against its slot, so the manual and instant seal modes seal blocks with an aura slot too.
 #[derive_impl(pallet_timestamp::config_preludes::TestDefaultConfig)]
-impl pallet_timestamp::Config for Runtime {}
+impl pallet_timestamp::Config for Runtime {
+    type OnTimestampSet = Aura;
+}
 
 // Implements the types required for the transaction payment pallet.
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
@@ -227,6 +275,52 @@ impl pallet_contracts::Config for Runtime {
     type Time = Timestamp;
 }
 
+impl pallet_jobs::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type Currency = Balances;
+    type JobDeposit = ConstU128<1_000>;
+    type WorkerDeposit = ConstU128<1_000>;
+    type JobTimeout = ConstU32<100>;
+    type MaxFuncNameLen = ConstU32<64>;
+    type WeightInfo = pallet_jobs::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_catalog::Config for Runtime {}
+
+impl pallet_session::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type ValidatorId = <Self as frame_system::Config>::AccountId;
+    type ValidatorIdOf = ConvertInto;
+    type ShouldEndSession = pallet_session::PeriodicSessions<ConstU32<SESSION_PERIOD>, ConstU32<0>>;
+    type NextSessionRotation =
+        pallet_session::PeriodicSessions<ConstU32<SESSION_PERIOD>, ConstU32<0>>;
+    // The validator set is fixed by the chain spec.
+    type SessionManager = ();
+    type SessionHandler = <SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+    type Keys = SessionKeys;
+    type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
+
+impl pallet_aura::Config for Runtime {
+    type AuthorityId = sp_consensus_aura::sr25519::AuthorityId;
+    type DisabledValidators = ();
+    type MaxAuthorities = ConstU32<MAX_AUTHORITIES>;
+    type AllowMultipleBlocksPerSlot = ConstBool<false>;
+    type SlotDuration = ConstU64<SLOT_DURATION>;
+}
+
+impl pallet_grandpa::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type WeightInfo = ();
+    type MaxAuthorities = ConstU32<MAX_AUTHORITIES>;
+    type MaxNominators = ConstU32<0>;
+    type MaxSetIdSessionEntries = ConstU64<0>;
+    // Equivocations aren't reported, so there are no key ownership proofs to check.
+    type KeyOwnerProof = Void;
+    type EquivocationReportSystem = ();
+}
+
 /// Some re-exports that the node side code needs to know. Some are useful in this context as well.
 ///
 /// Other types should preferably be private.
@@ -242,6 +336,7 @@ pub mod interface {
     pub type Nonce = <Runtime as frame_system::Config>::Nonce;
     pub type Hash = <Runtime as frame_system::Config>::Hash;
     pub type Balance = <Runtime as pallet_balances::Config>::Balance;
+    pub type BlockNumber = super::BlockNumber;
     pub type MinimumBalance = <Runtime as pallet_balances::Config>::ExistentialDeposit;
     pub type EventRecord = super::EventRecord;
 }
@@ -0,0 +1,132 @@
+//! # Catalog Pallet
+//!
+//! Instantiates the ink `Catalog` contract while building the genesis state and keeps its
+//! address, so clients of a fresh chain can find the catalog instead of deploying their own.
+//!
+//! The address only depends on the deploying account, the contract code, the constructor input
+//! and the salt, so chain specs that keep those fixed always deploy the catalog to the same one.
+//!
+//! The pallet also reads the catalog's jobs and workers for the `CatalogApi` runtime api, by
+//! calling the contract's read-only messages the way `ContractsApi::call` would.
+
+pub use pallet::*;
+
+#[frame::pallet]
+pub mod pallet {
+    use frame::{deps::sp_io::hashing::blake2_256, prelude::*};
+    use pallet_contracts::{Code, CollectEvents, DebugInfo, Determinism};
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_contracts::Config {}
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Address the catalog was instantiated at in the genesis block.
+    #[pallet::storage]
+    pub type CatalogAddress<T: Config> = StorageValue<_, T::AccountId>;
+
+    #[pallet::genesis_config]
+    #[derive(DefaultNoBound)]
+    pub struct GenesisConfig<T: Config> {
+        /// Account that instantiates the catalog, nothing is deployed without one.
+        pub deployer: Option<T::AccountId>,
+        /// Wasm code of the catalog.
+        pub code: Vec<u8>,
+        /// Selector of the constructor followed by its encoded arguments.
+        pub data: Vec<u8>,
+        pub salt: Vec<u8>,
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+        fn build(&self) {
+            let Some(deployer) = self.deployer.clone() else {
+                return;
+            };
+
+            let instantiated = pallet_contracts::Pallet::<T>::bare_instantiate(
+                deployer,
+                Default::default(),
+                T::BlockWeights::get().max_block,
+                None,
+                Code::Upload(self.code.clone()),
+                self.data.clone(),
+                self.salt.clone(),
+                DebugInfo::Skip,
+                CollectEvents::Skip,
+            )
+            .result
+            .expect("Catalog could not be instantiated at genesis");
+            assert!(
+                !instantiated.result.did_revert(),
+                "Catalog constructor reverted at genesis"
+            );
+
+            CatalogAddress::<T>::put(instantiated.account_id);
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Encoded `Option<JobRecord>` the catalog holds for job `id`.
+        pub fn job(catalog: Option<T::AccountId>, id: [u8; 32]) -> Option<Vec<u8>> {
+            Self::query(catalog, "job", id)
+        }
+
+        /// Encoded `Page<(HashId, JobRecord)>` of the catalog jobs still waiting for workers.
+        pub fn open_jobs(
+            catalog: Option<T::AccountId>,
+            cursor: u64,
+            limit: u32,
+        ) -> Option<Vec<u8>> {
+            Self::query(catalog, "open_jobs", (cursor, limit))
+        }
+
+        /// Encoded `(Option<WorkerProfile>, Reputation, Option<Stake>)` of `who` in the catalog.
+        pub fn worker_profile(catalog: Option<T::AccountId>, who: T::AccountId) -> Option<Vec<u8>> {
+            let mut profile = Self::query(catalog.clone(), "get_worker", &who)?;
+            profile.extend(Self::query(catalog.clone(), "get_reputation", &who)?);
+            profile.extend(Self::query(catalog, "get_stake", &who)?);
+
+            Some(profile)
+        }
+
+        /// Output of the read-only catalog message `message` called with `input` on `catalog`, or
+        /// on the genesis catalog if none is given. `None` if there is no such catalog or the call
+        /// fails.
+        fn query(
+            catalog: Option<T::AccountId>,
+            message: &str,
+            input: impl Encode,
+        ) -> Option<Vec<u8>> {
+            let catalog = catalog.or_else(CatalogAddress::<T>::get)?;
+
+            // ink selects a message by the first four bytes of the blake2 hash of its name.
+            let mut data = blake2_256(message.as_bytes())[..4].to_vec();
+            input.encode_to(&mut data);
+
+            let output = pallet_contracts::Pallet::<T>::bare_call(
+                catalog.clone(),
+                catalog,
+                Default::default(),
+                T::BlockWeights::get().max_block,
+                None,
+                data,
+                DebugInfo::Skip,
+                CollectEvents::Skip,
+                Determinism::Enforced,
+            )
+            .result
+            .ok()?;
+            if output.did_revert() {
+                return None;
+            }
+
+            // Messages return their value wrapped in an ink `MessageResult`.
+            match output.data.split_first()? {
+                (0, value) => Some(value.to_vec()),
+                _ => None,
+            }
+        }
+    }
+}
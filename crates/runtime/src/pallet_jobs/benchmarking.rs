@@ -0,0 +1,141 @@
+//! Benchmarks for the jobs pallet, run through `frame-omni-bencher` to generate `weights.rs`.
+
+use super::*;
+use frame::{
+    arithmetic::Saturating,
+    deps::{
+        frame_benchmarking::v2::*,
+        frame_support::traits::fungible::{Inspect, Mutate},
+        frame_system::RawOrigin,
+    },
+    prelude::*,
+};
+
+type Currency<T> = <T as Config>::Currency;
+
+#[benchmarks(where T::Currency: Mutate<T::AccountId>)]
+mod benchmarks {
+    use super::*;
+
+    /// Account holding enough to escrow a job and put up both deposits.
+    fn funded_account<T: Config>(name: &'static str) -> T::AccountId
+    where
+        T::Currency: Mutate<T::AccountId>,
+    {
+        let who = account(name, 0, 0);
+        let balance = Currency::<T>::minimum_balance()
+            .saturating_add(value::<T>())
+            .saturating_add(T::JobDeposit::get())
+            .saturating_add(T::WorkerDeposit::get())
+            .saturating_mul(2u32.into());
+        Currency::<T>::set_balance(&who, balance);
+        who
+    }
+
+    fn value<T: Config>() -> BalanceOf<T> {
+        Currency::<T>::minimum_balance().saturating_mul(100u32.into())
+    }
+
+    fn func_name<T: Config>() -> BoundedVec<u8, T::MaxFuncNameLen> {
+        vec![0; T::MaxFuncNameLen::get() as usize]
+            .try_into()
+            .unwrap()
+    }
+
+    fn submitted_job<T: Config>(requester: &T::AccountId) -> JobId
+    where
+        T::Currency: Mutate<T::AccountId>,
+    {
+        Pallet::<T>::submit_job(
+            RawOrigin::Signed(requester.clone()).into(),
+            [0; 32],
+            [1; 32],
+            func_name::<T>(),
+            value::<T>(),
+        )
+        .unwrap();
+
+        Jobs::<T>::iter_keys().next().unwrap()
+    }
+
+    fn accepted_job<T: Config>(requester: &T::AccountId, worker: &T::AccountId) -> JobId
+    where
+        T::Currency: Mutate<T::AccountId>,
+    {
+        let id = submitted_job::<T>(requester);
+        Pallet::<T>::accept_job(RawOrigin::Signed(worker.clone()).into(), id).unwrap();
+        id
+    }
+
+    fn pass_deadline<T: Config>(id: JobId) {
+        let deadline = Jobs::<T>::get(id).unwrap().deadline;
+        frame_system::Pallet::<T>::set_block_number(deadline);
+    }
+
+    #[benchmark]
+    fn submit_job() {
+        let requester = funded_account::<T>("requester");
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(requester.clone()),
+            [0; 32],
+            [1; 32],
+            func_name::<T>(),
+            value::<T>(),
+        );
+
+        assert_eq!(Nonces::<T>::get(&requester), 1);
+    }
+
+    #[benchmark]
+    fn accept_job() {
+        let requester = funded_account::<T>("requester");
+        let worker = funded_account::<T>("worker");
+        let id = submitted_job::<T>(&requester);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(worker.clone()), id);
+
+        assert_eq!(Jobs::<T>::get(id).unwrap().worker, Some(worker));
+    }
+
+    #[benchmark]
+    fn pay_worker() {
+        let requester = funded_account::<T>("requester");
+        let worker = funded_account::<T>("worker");
+        let id = accepted_job::<T>(&requester, &worker);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(requester), id);
+
+        assert!(!Jobs::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn cancel_job() {
+        let requester = funded_account::<T>("requester");
+        let id = submitted_job::<T>(&requester);
+        pass_deadline::<T>(id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(requester), id);
+
+        assert!(!Jobs::<T>::contains_key(id));
+    }
+
+    #[benchmark]
+    fn reclaim_job() {
+        let requester = funded_account::<T>("requester");
+        let worker = funded_account::<T>("worker");
+        let id = accepted_job::<T>(&requester, &worker);
+        pass_deadline::<T>(id);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(requester), id);
+
+        assert!(!Jobs::<T>::contains_key(id));
+    }
+
+    impl_benchmark_test_suite!(Pallet, super::tests::new_test_ext(), crate::Runtime);
+}
@@ -0,0 +1,393 @@
+//! # Jobs Pallet
+//!
+//! Native counterpart of the ink `Catalog` contract. A requester submits a job and escrows its
+//! payment, a worker accepts it, and the requester pays the worker once it has the result. Jobs
+//! no worker accepts before their deadline can be cancelled and the escrow returned.
+//!
+//! Every job also holds a storage deposit from its requester, which is released when the job is
+//! paid or cancelled and its entry removed from storage.
+//!
+//! A worker puts up a deposit of its own when it accepts a job and gets it back once it is paid.
+//! If the job is still unpaid at its deadline, the requester can reclaim the escrow and the
+//! worker's deposit is forfeited to them.
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+#[frame::pallet]
+pub mod pallet {
+    use super::WeightInfo;
+    use frame::{
+        arithmetic::{Saturating, Zero},
+        deps::{
+            frame_support::traits::{
+                fungible::{Inspect, InspectHold, MutateHold},
+                tokens::{Fortitude, Precision, Restriction},
+            },
+            sp_io::hashing::keccak_256,
+        },
+        prelude::*,
+    };
+
+    /// Id of a job, computed the same way as the `Catalog` contract does.
+    pub type JobId = [u8; 32];
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+        type RuntimeHoldReason: From<HoldReason>;
+
+        /// Currency used to escrow job payments and storage deposits.
+        type Currency: MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+        /// Deposit held from the requester for the storage a job takes up.
+        #[pallet::constant]
+        type JobDeposit: Get<BalanceOf<Self>>;
+
+        /// Deposit held from a worker when it accepts a job, forfeited to the requester if the
+        /// job is reclaimed.
+        #[pallet::constant]
+        type WorkerDeposit: Get<BalanceOf<Self>>;
+
+        /// Number of blocks a job stays open for a worker to accept it, and then for the
+        /// requester to pay its worker.
+        #[pallet::constant]
+        type JobTimeout: Get<BlockNumberFor<Self>>;
+
+        #[pallet::constant]
+        type MaxFuncNameLen: Get<u32>;
+
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::composite_enum]
+    pub enum HoldReason {
+        /// Payment escrowed for the worker of a job.
+        JobEscrow,
+        /// Deposit for the storage taken up by a job.
+        JobDeposit,
+        /// Deposit a worker puts up for a job it accepted.
+        WorkerDeposit,
+    }
+
+    #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub enum JobState {
+        Submitted,
+        Accepted,
+    }
+
+    #[derive(
+        Encode, Decode, MaxEncodedLen, TypeInfo, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+    )]
+    #[scale_info(skip_type_params(T))]
+    pub struct Job<T: Config> {
+        pub requester: T::AccountId,
+        pub worker: Option<T::AccountId>,
+        pub value: BalanceOf<T>,
+        pub deposit: BalanceOf<T>,
+        pub worker_deposit: BalanceOf<T>,
+        pub deadline: BlockNumberFor<T>,
+        pub state: JobState,
+    }
+
+    #[pallet::storage]
+    pub type Jobs<T: Config> = StorageMap<_, Blake2_128Concat, JobId, Job<T>>;
+
+    /// Ids of the jobs still waiting for a worker, so they can be paged without reading `Jobs`.
+    #[pallet::storage]
+    pub type OpenJobs<T: Config> = StorageMap<_, Blake2_128Concat, JobId, ()>;
+
+    #[pallet::storage]
+    pub type Nonces<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        JobSubmitted {
+            who: T::AccountId,
+            id: JobId,
+            nonce: u64,
+            value: BalanceOf<T>,
+            deadline: BlockNumberFor<T>,
+        },
+        JobAccepted {
+            who: T::AccountId,
+            id: JobId,
+            deadline: BlockNumberFor<T>,
+        },
+        WorkerPaid {
+            who: T::AccountId,
+            id: JobId,
+            value: BalanceOf<T>,
+        },
+        JobCancelled {
+            who: T::AccountId,
+            id: JobId,
+            value: BalanceOf<T>,
+        },
+        JobReclaimed {
+            who: T::AccountId,
+            id: JobId,
+            value: BalanceOf<T>,
+            forfeited: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        JobAlreadyExists,
+        JobNotFound,
+        NotJobRequester,
+        NotAssignedWorker,
+        InvalidJobState,
+        DeadlinePassed,
+        DeadlineNotReached,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Submits a job, holding `value` as the worker's payment along with the storage deposit.
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::submit_job())]
+        pub fn submit_job(
+            origin: OriginFor<T>,
+            code_hash: [u8; 32],
+            params_hash: [u8; 32],
+            func_name: BoundedVec<u8, T::MaxFuncNameLen>,
+            value: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let nonce = Nonces::<T>::get(&who);
+            let id = keccak_256(&(code_hash, params_hash, &func_name, &who, nonce).encode());
+            ensure!(!Jobs::<T>::contains_key(id), Error::<T>::JobAlreadyExists);
+
+            let deposit = T::JobDeposit::get();
+            T::Currency::hold(&HoldReason::JobEscrow.into(), &who, value)?;
+            T::Currency::hold(&HoldReason::JobDeposit.into(), &who, deposit)?;
+
+            let deadline = Self::deadline();
+            Jobs::<T>::insert(
+                id,
+                Job {
+                    requester: who.clone(),
+                    worker: None,
+                    value,
+                    deposit,
+                    worker_deposit: Zero::zero(),
+                    deadline,
+                    state: JobState::Submitted,
+                },
+            );
+            OpenJobs::<T>::insert(id, ());
+            Nonces::<T>::insert(&who, nonce.saturating_add(1));
+
+            Self::deposit_event(Event::JobSubmitted {
+                who,
+                id,
+                nonce,
+                value,
+                deadline,
+            });
+
+            Ok(())
+        }
+
+        /// Assigns an open job to the caller, holding its worker deposit, and restarts its
+        /// deadline.
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::accept_job())]
+        pub fn accept_job(origin: OriginFor<T>, id: JobId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut job = Jobs::<T>::get(id).ok_or(Error::<T>::JobNotFound)?;
+            ensure!(
+                job.state == JobState::Submitted,
+                Error::<T>::InvalidJobState
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() < job.deadline,
+                Error::<T>::DeadlinePassed
+            );
+
+            let worker_deposit = T::WorkerDeposit::get();
+            T::Currency::hold(&HoldReason::WorkerDeposit.into(), &who, worker_deposit)?;
+
+            job.worker = Some(who.clone());
+            job.worker_deposit = worker_deposit;
+            job.deadline = Self::deadline();
+            job.state = JobState::Accepted;
+            let deadline = job.deadline;
+            Jobs::<T>::insert(id, job);
+            OpenJobs::<T>::remove(id);
+
+            Self::deposit_event(Event::JobAccepted { who, id, deadline });
+
+            Ok(())
+        }
+
+        /// Releases the escrow of an accepted job to its worker and returns both deposits.
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::pay_worker())]
+        pub fn pay_worker(origin: OriginFor<T>, id: JobId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let job = Jobs::<T>::get(id).ok_or(Error::<T>::JobNotFound)?;
+            ensure!(job.requester == who, Error::<T>::NotJobRequester);
+            ensure!(job.state == JobState::Accepted, Error::<T>::InvalidJobState);
+            let worker = job.worker.ok_or(Error::<T>::NotAssignedWorker)?;
+
+            T::Currency::transfer_on_hold(
+                &HoldReason::JobEscrow.into(),
+                &who,
+                &worker,
+                job.value,
+                Precision::Exact,
+                Restriction::Free,
+                Fortitude::Polite,
+            )?;
+            T::Currency::release(
+                &HoldReason::JobDeposit.into(),
+                &who,
+                job.deposit,
+                Precision::Exact,
+            )?;
+            T::Currency::release(
+                &HoldReason::WorkerDeposit.into(),
+                &worker,
+                job.worker_deposit,
+                Precision::Exact,
+            )?;
+            Jobs::<T>::remove(id);
+
+            Self::deposit_event(Event::WorkerPaid {
+                who: worker,
+                id,
+                value: job.value,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the escrow and storage deposit of a job no worker accepted before its
+        /// deadline.
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::cancel_job())]
+        pub fn cancel_job(origin: OriginFor<T>, id: JobId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let job = Jobs::<T>::get(id).ok_or(Error::<T>::JobNotFound)?;
+            ensure!(job.requester == who, Error::<T>::NotJobRequester);
+            ensure!(
+                job.state == JobState::Submitted,
+                Error::<T>::InvalidJobState
+            );
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= job.deadline,
+                Error::<T>::DeadlineNotReached
+            );
+
+            T::Currency::release(
+                &HoldReason::JobEscrow.into(),
+                &who,
+                job.value,
+                Precision::Exact,
+            )?;
+            T::Currency::release(
+                &HoldReason::JobDeposit.into(),
+                &who,
+                job.deposit,
+                Precision::Exact,
+            )?;
+            Jobs::<T>::remove(id);
+            OpenJobs::<T>::remove(id);
+
+            Self::deposit_event(Event::JobCancelled {
+                who,
+                id,
+                value: job.value,
+            });
+
+            Ok(())
+        }
+
+        /// Returns the escrow and storage deposit of an accepted job its requester has not paid
+        /// by the deadline, and hands them the worker's deposit.
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::reclaim_job())]
+        pub fn reclaim_job(origin: OriginFor<T>, id: JobId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let job = Jobs::<T>::get(id).ok_or(Error::<T>::JobNotFound)?;
+            ensure!(job.requester == who, Error::<T>::NotJobRequester);
+            ensure!(job.state == JobState::Accepted, Error::<T>::InvalidJobState);
+            ensure!(
+                frame_system::Pallet::<T>::block_number() >= job.deadline,
+                Error::<T>::DeadlineNotReached
+            );
+            let worker = job.worker.ok_or(Error::<T>::NotAssignedWorker)?;
+
+            T::Currency::release(
+                &HoldReason::JobEscrow.into(),
+                &who,
+                job.value,
+                Precision::Exact,
+            )?;
+            T::Currency::release(
+                &HoldReason::JobDeposit.into(),
+                &who,
+                job.deposit,
+                Precision::Exact,
+            )?;
+            T::Currency::transfer_on_hold(
+                &HoldReason::WorkerDeposit.into(),
+                &worker,
+                &who,
+                job.worker_deposit,
+                Precision::Exact,
+                Restriction::Free,
+                Fortitude::Polite,
+            )?;
+            Jobs::<T>::remove(id);
+
+            Self::deposit_event(Event::JobReclaimed {
+                who,
+                id,
+                value: job.value,
+                forfeited: job.worker_deposit,
+            });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Ids of up to `limit` jobs still waiting for a worker, in storage order. Passing the
+        /// last id of a page as `start_after` returns the next one.
+        pub fn open_jobs(start_after: Option<JobId>, limit: u32) -> Vec<JobId> {
+            let ids = match start_after {
+                Some(id) => OpenJobs::<T>::iter_keys_from(OpenJobs::<T>::hashed_key_for(id)),
+                None => OpenJobs::<T>::iter_keys(),
+            };
+
+            ids.take(limit as usize).collect()
+        }
+
+        /// Total payment `who` currently has escrowed for its jobs.
+        pub fn escrow_balance(who: &T::AccountId) -> BalanceOf<T> {
+            T::Currency::balance_on_hold(&HoldReason::JobEscrow.into(), who)
+        }
+
+        fn deadline() -> BlockNumberFor<T> {
+            frame_system::Pallet::<T>::block_number().saturating_add(T::JobTimeout::get())
+        }
+    }
+}
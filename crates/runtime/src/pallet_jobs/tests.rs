@@ -0,0 +1,274 @@
+use super::{Error, Event, HoldReason, JobState, Jobs as JobStore, OpenJobs};
+use crate::{interface::Balance, Balances, Jobs, Runtime, RuntimeOrigin, System};
+use frame::{
+    deps::{
+        frame_support::traits::fungible::{Inspect, InspectHold},
+        sp_runtime::AccountId32,
+    },
+    prelude::*,
+    testing_prelude::*,
+};
+
+const ALICE: AccountId32 = AccountId32::new([1; 32]);
+const BOB: AccountId32 = AccountId32::new([2; 32]);
+const INITIAL_BALANCE: Balance = 10_000;
+const VALUE: Balance = 100;
+
+pub(super) fn new_test_ext() -> TestState {
+    let mut storage = frame_system::GenesisConfig::<Runtime>::default()
+        .build_storage()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Runtime> {
+        balances: vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = TestState::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+fn submit_job() -> [u8; 32] {
+    assert_ok!(Jobs::submit_job(
+        RuntimeOrigin::signed(ALICE),
+        [0; 32],
+        [1; 32],
+        b"foo".to_vec().try_into().unwrap(),
+        VALUE,
+    ));
+
+    JobStore::<Runtime>::iter_keys().next().unwrap()
+}
+
+fn deposit() -> Balance {
+    <<Runtime as super::Config>::JobDeposit as Get<_>>::get()
+}
+
+fn worker_deposit() -> Balance {
+    <<Runtime as super::Config>::WorkerDeposit as Get<_>>::get()
+}
+
+fn timeout() -> u32 {
+    <<Runtime as super::Config>::JobTimeout as Get<_>>::get()
+}
+
+#[test]
+fn submit_job_holds_escrow_and_deposit() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::JobEscrow.into(), &ALICE),
+            VALUE
+        );
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::JobDeposit.into(), &ALICE),
+            deposit()
+        );
+        assert_eq!(
+            JobStore::<Runtime>::get(id).unwrap().state,
+            JobState::Submitted
+        );
+        assert_eq!(Jobs::open_jobs(None, 10), vec![id]);
+        assert_eq!(Jobs::escrow_balance(&ALICE), VALUE);
+        System::assert_last_event(
+            Event::JobSubmitted {
+                who: ALICE,
+                id,
+                nonce: 0,
+                value: VALUE,
+                deadline: 1 + timeout(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn submit_job_fails_without_funds_to_hold() {
+    new_test_ext().execute_with(|| {
+        assert!(Jobs::submit_job(
+            RuntimeOrigin::signed(ALICE),
+            [0; 32],
+            [1; 32],
+            b"foo".to_vec().try_into().unwrap(),
+            INITIAL_BALANCE,
+        )
+        .is_err());
+    });
+}
+
+#[test]
+fn pay_worker_transfers_escrow_and_releases_deposit() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+        assert_ok!(Jobs::accept_job(RuntimeOrigin::signed(BOB), id));
+
+        assert_eq!(
+            Balances::balance_on_hold(&HoldReason::WorkerDeposit.into(), &BOB),
+            worker_deposit()
+        );
+
+        assert_ok!(Jobs::pay_worker(RuntimeOrigin::signed(ALICE), id));
+
+        assert_eq!(Balances::balance(&ALICE), INITIAL_BALANCE - VALUE);
+        assert_eq!(Balances::balance(&BOB), INITIAL_BALANCE + VALUE);
+        assert_eq!(Balances::total_balance_on_hold(&ALICE), 0);
+        assert_eq!(Balances::total_balance_on_hold(&BOB), 0);
+        assert!(!JobStore::<Runtime>::contains_key(id));
+        System::assert_last_event(
+            Event::WorkerPaid {
+                who: BOB,
+                id,
+                value: VALUE,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn pay_worker_fails_for_job_not_accepted_or_other_requester() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+
+        assert_noop!(
+            Jobs::pay_worker(RuntimeOrigin::signed(ALICE), id),
+            Error::<Runtime>::InvalidJobState
+        );
+
+        assert_ok!(Jobs::accept_job(RuntimeOrigin::signed(BOB), id));
+        assert_noop!(
+            Jobs::pay_worker(RuntimeOrigin::signed(BOB), id),
+            Error::<Runtime>::NotJobRequester
+        );
+    });
+}
+
+#[test]
+fn accept_job_fails_after_deadline() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+        System::set_block_number(1 + timeout());
+
+        assert_noop!(
+            Jobs::accept_job(RuntimeOrigin::signed(BOB), id),
+            Error::<Runtime>::DeadlinePassed
+        );
+    });
+}
+
+#[test]
+fn cancel_job_refunds_requester_after_deadline() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+
+        assert_noop!(
+            Jobs::cancel_job(RuntimeOrigin::signed(ALICE), id),
+            Error::<Runtime>::DeadlineNotReached
+        );
+
+        System::set_block_number(1 + timeout());
+        assert_ok!(Jobs::cancel_job(RuntimeOrigin::signed(ALICE), id));
+
+        assert_eq!(Balances::balance(&ALICE), INITIAL_BALANCE);
+        assert!(!JobStore::<Runtime>::contains_key(id));
+        assert!(Jobs::open_jobs(None, 10).is_empty());
+    });
+}
+
+#[test]
+fn reclaim_job_refunds_requester_with_worker_deposit_after_deadline() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+        assert_ok!(Jobs::accept_job(RuntimeOrigin::signed(BOB), id));
+
+        assert_noop!(
+            Jobs::reclaim_job(RuntimeOrigin::signed(ALICE), id),
+            Error::<Runtime>::DeadlineNotReached
+        );
+
+        System::set_block_number(1 + timeout());
+        assert_noop!(
+            Jobs::reclaim_job(RuntimeOrigin::signed(BOB), id),
+            Error::<Runtime>::NotJobRequester
+        );
+        assert_ok!(Jobs::reclaim_job(RuntimeOrigin::signed(ALICE), id));
+
+        assert_eq!(
+            Balances::balance(&ALICE),
+            INITIAL_BALANCE + worker_deposit()
+        );
+        assert_eq!(Balances::balance(&BOB), INITIAL_BALANCE - worker_deposit());
+        assert_eq!(Balances::total_balance_on_hold(&ALICE), 0);
+        assert!(!JobStore::<Runtime>::contains_key(id));
+        System::assert_last_event(
+            Event::JobReclaimed {
+                who: ALICE,
+                id,
+                value: VALUE,
+                forfeited: worker_deposit(),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn reclaim_job_fails_for_open_job() {
+    new_test_ext().execute_with(|| {
+        let id = submit_job();
+        System::set_block_number(1 + timeout());
+
+        assert_noop!(
+            Jobs::reclaim_job(RuntimeOrigin::signed(ALICE), id),
+            Error::<Runtime>::InvalidJobState
+        );
+    });
+}
+
+#[test]
+fn open_jobs_pages_through_jobs_waiting_for_a_worker() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..3 {
+            submit_job();
+        }
+        let accepted = JobStore::<Runtime>::iter_keys().next().unwrap();
+        assert_ok!(Jobs::accept_job(RuntimeOrigin::signed(BOB), accepted));
+
+        let first_page = Jobs::open_jobs(None, 1);
+        let second_page = Jobs::open_jobs(first_page.last().copied(), 10);
+
+        assert_eq!(first_page.len(), 1);
+        assert_eq!(second_page.len(), 1);
+        assert_ne!(first_page, second_page);
+        assert!(!first_page.contains(&accepted) && !second_page.contains(&accepted));
+        assert!(!OpenJobs::<Runtime>::contains_key(accepted));
+    });
+}
+
+#[test]
+fn weights_assume_max_encoded_len() {
+    use frame::deps::frame_support::traits::StorageInfoTrait;
+
+    let max_sizes: Vec<_> = Jobs::storage_info()
+        .into_iter()
+        .chain(Balances::storage_info())
+        .chain(System::storage_info())
+        .map(|info| (info.storage_name, info.max_size))
+        .collect();
+
+    for (name, size) in [
+        ("Jobs", 166),
+        ("OpenJobs", 48),
+        ("Nonces", 56),
+        ("Holds", 139),
+        ("Account", 128),
+    ] {
+        assert!(
+            max_sizes.contains(&(name.as_bytes().to_vec(), Some(size))),
+            "{name} is no longer {size} bytes, update weights.rs"
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Placeholder weights for the jobs pallet. They are estimates, not benchmark output: the ref
+//! times have not been measured, and the proof sizes only add up the `MaxEncodedLen` of the
+//! storage each call reads (`Jobs` 166, `Nonces` 56, `Balances::Holds` 139, `System::Account`
+//! 128 bytes, each plus its key), which `tests::weights_assume_max_encoded_len` keeps in sync.
+//!
+//! Replace this file with the output of the benchmarks in `benchmarking.rs` before relying on it,
+//! from a runtime built with the `runtime-benchmarks` feature:
+//!
+//! ```sh
+//! frame-omni-bencher v1 benchmark pallet \
+//!     --runtime target/release/wbuild/runtime/runtime.compact.compressed.wasm \
+//!     --pallet pallet_jobs --extrinsic "*" \
+//!     --output crates/runtime/src/pallet_jobs/weights.rs
+//! ```
+
+use core::marker::PhantomData;
+use frame::{deps::frame_support::weights::constants::RocksDbWeight, prelude::*};
+
+pub trait WeightInfo {
+    fn submit_job() -> Weight;
+    fn accept_job() -> Weight;
+    fn pay_worker() -> Weight;
+    fn cancel_job() -> Weight;
+    fn reclaim_job() -> Weight;
+}
+
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// Reads the requester's nonce and writes the job, its `OpenJobs` entry, the nonce and the
+    /// requester's holds and account.
+    fn submit_job() -> Weight {
+        Weight::from_parts(60_000_000, 11_379)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    /// Reads and writes the job and the worker's holds and account, and removes the `OpenJobs`
+    /// entry.
+    fn accept_job() -> Weight {
+        Weight::from_parts(40_000_000, 8_848)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    /// Reads and writes the job and the holds and accounts of both the requester and the worker.
+    fn pay_worker() -> Weight {
+        Weight::from_parts(85_000_000, 14_065)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+
+    /// Reads and writes the job and the requester's holds and account, and removes the `OpenJobs`
+    /// entry.
+    fn cancel_job() -> Weight {
+        Weight::from_parts(50_000_000, 8_848)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+
+    /// Reads and writes the job and the holds and accounts of both the requester and the worker.
+    fn reclaim_job() -> Weight {
+        Weight::from_parts(85_000_000, 14_065)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+}
+
+impl WeightInfo for () {
+    fn submit_job() -> Weight {
+        Weight::from_parts(60_000_000, 11_379)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn accept_job() -> Weight {
+        Weight::from_parts(40_000_000, 8_848)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn pay_worker() -> Weight {
+        Weight::from_parts(85_000_000, 14_065)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+
+    fn cancel_job() -> Weight {
+        Weight::from_parts(50_000_000, 8_848)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+
+    fn reclaim_job() -> Weight {
+        Weight::from_parts(85_000_000, 14_065)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
     interface::{self, AccountId, Balance, EventRecord, Hash},
-    Block, BlockNumber, Contracts, Header, InherentDataExt, Runtime, RuntimeBlockWeights,
-    RuntimeExecutive, RuntimeGenesisConfig, System, TransactionPayment, CONTRACTS_DEBUG_OUTPUT,
-    CONTRACTS_EVENTS, VERSION,
+    pallet_catalog,
+    pallet_jobs::{self, Job, JobId},
+    Aura, Block, BlockNumber, Catalog, Contracts, Grandpa, Header, InherentDataExt, Jobs, Runtime,
+    RuntimeBlockWeights, RuntimeExecutive, RuntimeGenesisConfig, SessionKeys, System,
+    TransactionPayment, CONTRACTS_DEBUG_OUTPUT, CONTRACTS_EVENTS, VERSION,
 };
 
 use frame::{
@@ -10,13 +12,58 @@ use frame::{
     prelude::*,
     runtime::{
         apis::{
-            self, impl_runtime_apis, ApplyExtrinsicResult, CheckInherentsResult,
+            self, decl_runtime_apis, impl_runtime_apis, ApplyExtrinsicResult, CheckInherentsResult,
             ExtrinsicInclusionMode, OpaqueMetadata,
         },
         prelude::*,
     },
 };
 use pallet_transaction_payment::{FeeDetails, RuntimeDispatchInfo};
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+#[cfg(feature = "runtime-benchmarks")]
+use {
+    crate::AllPalletsWithSystem,
+    frame::deps::{frame_benchmarking, frame_support},
+};
+
+decl_runtime_apis! {
+    /// Read access to the marketplace state kept by the jobs pallet.
+    pub trait JobsApi {
+        /// The job stored under `id`, if it is still open or accepted.
+        fn job_status(id: JobId) -> Option<Job<Runtime>>;
+
+        /// Ids of up to `limit` jobs still waiting for a worker, after `start_after` if given.
+        fn open_jobs(start_after: Option<JobId>, limit: u32) -> Vec<JobId>;
+
+        /// Total payment `who` currently has escrowed for its jobs.
+        fn escrow_balance(who: AccountId) -> Balance;
+    }
+
+    /// Locates the catalog contract deployed at genesis and reads its jobs and workers.
+    ///
+    /// The reads return the catalog's values SCALE encoded, to be decoded with the `catalog`
+    /// crate's types. They query `catalog`, or the genesis catalog if it is `None`, and return
+    /// `None` if there is no such catalog or the query fails.
+    pub trait CatalogApi {
+        /// Address of the catalog, if the chain spec deployed one.
+        fn catalog_address() -> Option<AccountId>;
+
+        /// Encoded `Option<JobRecord>` of catalog job `id`.
+        fn catalog_job(catalog: Option<AccountId>, id: JobId) -> Option<Vec<u8>>;
+
+        /// Encoded `Page<(HashId, JobRecord)>` of the catalog jobs still waiting for workers,
+        /// starting at `cursor`.
+        fn catalog_open_jobs(
+            catalog: Option<AccountId>,
+            cursor: u64,
+            limit: u32,
+        ) -> Option<Vec<u8>>;
+
+        /// Encoded `(Option<WorkerProfile>, Reputation, Option<Stake>)` of `who` in the catalog.
+        fn worker_profile(catalog: Option<AccountId>, who: AccountId) -> Option<Vec<u8>>;
+    }
+}
 
 impl_runtime_apis! {
     impl apis::Core<Block> for Runtime {
@@ -84,14 +131,49 @@ impl_runtime_apis! {
     }
 
     impl apis::SessionKeys<Block> for Runtime {
-        fn generate_session_keys(_seed: Option<Vec<u8>>) -> Vec<u8> {
-            Default::default()
+        fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
+            SessionKeys::generate(seed)
         }
 
         fn decode_session_keys(
-            _encoded: Vec<u8>,
+            encoded: Vec<u8>,
         ) -> Option<Vec<(Vec<u8>, apis::KeyTypeId)>> {
-            Default::default()
+            SessionKeys::decode_into_raw_public_keys(&encoded)
+        }
+    }
+
+    impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
+        fn slot_duration() -> sp_consensus_aura::SlotDuration {
+            sp_consensus_aura::SlotDuration::from_millis(Aura::slot_duration())
+        }
+
+        fn authorities() -> Vec<AuraId> {
+            pallet_aura::Authorities::<Runtime>::get().into_inner()
+        }
+    }
+
+    impl sp_consensus_grandpa::GrandpaApi<Block> for Runtime {
+        fn grandpa_authorities() -> sp_consensus_grandpa::AuthorityList {
+            Grandpa::grandpa_authorities()
+        }
+
+        fn current_set_id() -> sp_consensus_grandpa::SetId {
+            Grandpa::current_set_id()
+        }
+
+        fn submit_report_equivocation_unsigned_extrinsic(
+            _equivocation_proof: sp_consensus_grandpa::EquivocationProof<Hash, BlockNumber>,
+            _key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+        ) -> Option<()> {
+            None
+        }
+
+        fn generate_key_ownership_proof(
+            _set_id: sp_consensus_grandpa::SetId,
+            _authority_id: GrandpaId,
+        ) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
+            // Equivocations aren't reported, see `KeyOwnerProof` in the grandpa config.
+            None
         }
     }
 
@@ -185,6 +267,72 @@ impl_runtime_apis! {
     }
 }
 
+    impl self::JobsApi<Block> for Runtime {
+        fn job_status(id: JobId) -> Option<Job<Runtime>> {
+            pallet_jobs::Jobs::<Runtime>::get(id)
+        }
+
+        fn open_jobs(start_after: Option<JobId>, limit: u32) -> Vec<JobId> {
+            Jobs::open_jobs(start_after, limit)
+        }
+
+        fn escrow_balance(who: AccountId) -> Balance {
+            Jobs::escrow_balance(&who)
+        }
+    }
+
+    impl self::CatalogApi<Block> for Runtime {
+        fn catalog_address() -> Option<AccountId> {
+            pallet_catalog::CatalogAddress::<Runtime>::get()
+        }
+
+        fn catalog_job(catalog: Option<AccountId>, id: JobId) -> Option<Vec<u8>> {
+            Catalog::job(catalog, id)
+        }
+
+        fn catalog_open_jobs(
+            catalog: Option<AccountId>,
+            cursor: u64,
+            limit: u32,
+        ) -> Option<Vec<u8>> {
+            Catalog::open_jobs(catalog, cursor, limit)
+        }
+
+        fn worker_profile(catalog: Option<AccountId>, who: AccountId) -> Option<Vec<u8>> {
+            Catalog::worker_profile(catalog, who)
+        }
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    impl frame_benchmarking::Benchmark<Block> for Runtime {
+        fn benchmark_metadata(extra: bool) -> (
+            Vec<frame_benchmarking::BenchmarkList>,
+            Vec<frame_support::traits::StorageInfo>,
+        ) {
+            use frame_benchmarking::{BenchmarkList, Benchmarking};
+            use frame_support::traits::StorageInfoTrait;
+
+            let mut list = Vec::<BenchmarkList>::new();
+            list_benchmarks!(list, extra);
+
+            (list, AllPalletsWithSystem::storage_info())
+        }
+
+        fn dispatch_benchmark(
+            config: frame_benchmarking::BenchmarkConfig,
+        ) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+            use frame_benchmarking::{BenchmarkBatch, Benchmarking};
+            use frame_support::traits::WhitelistedStorageKeys;
+
+            let whitelist = AllPalletsWithSystem::whitelisted_storage_keys();
+            let mut batches = Vec::<BenchmarkBatch>::new();
+            let params = (&config, &whitelist);
+            add_benchmarks!(params, batches);
+
+            Ok(batches)
+        }
+    }
+
     impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
         fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
             build_state::<RuntimeGenesisConfig>(config)
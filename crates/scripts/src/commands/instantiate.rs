@@ -1,7 +1,7 @@
 use clap::Parser;
 use ink::env::DefaultEnvironment;
 use std::str::FromStr;
-use subxt::SubstrateConfig;
+use subxt::{tx::Signer, utils::AccountId32, SubstrateConfig};
 use subxt_signer::{sr25519::Keypair, SecretUri};
 use utils::services::contract_client::Client;
 
@@ -12,6 +12,10 @@ pub struct InstantiateCmd {
 
     #[arg(long)]
     file: String,
+
+    /// SS58 address of the account that owns the contract, defaults to the signer
+    #[arg(long)]
+    owner: Option<AccountId32>,
 }
 
 impl InstantiateCmd {
@@ -23,7 +27,15 @@ impl InstantiateCmd {
                 .await
                 .unwrap();
 
-        let contract_address = client.instantiate("new", 0).await.unwrap();
+        let owner = self
+            .owner
+            .clone()
+            .unwrap_or_else(|| Signer::<SubstrateConfig>::account_id(&signer));
+
+        let contract_address = client
+            .instantiate("new_with_owner", &owner, 0)
+            .await
+            .unwrap();
 
         println!("{}", contract_address);
     }
@@ -6,21 +6,27 @@ use crate::{
     },
     ink_project::{InkProject, InkProjectError},
 };
-use codec::{Decode, Encode};
+use catalog::catalog::{HashId, JobRecord, Reputation, Stake, WorkerProfile};
+use codec::{Decode, DecodeAll, Encode};
 use futures::{stream::iter, Stream, TryStreamExt};
 use pallet_contracts::{Code, ContractAccessError, ContractExecResult, ContractInstantiateResult};
 use std::{fmt::Display, fs::File, io::BufReader, marker::PhantomData};
 use std::{future::Future, marker::Sync};
 
 use ink::{
-    env::Environment,
+    env::{Environment, Event},
     primitives::{LangError, MessageResult},
 };
 use serde::Serialize;
+use sp_runtime::traits::Verify;
 use subxt::{
-    backend::{legacy::LegacyRpcMethods, rpc::RpcClient},
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::{rpc_params, RpcClient},
+    },
     blocks::{Block, ExtrinsicDetails, ExtrinsicEvents},
     config::{Config, DefaultExtrinsicParams, ExtrinsicParams},
+    events::EventDetails,
     ext::{scale_decode::IntoVisitor, scale_encode::EncodeAsType},
     tx::{Payload, Signer, TxPayload},
     utils::{AccountId32, MultiAddress},
@@ -40,7 +46,7 @@ pub trait ContractClient {
         Output = Result<impl Stream<Item = Result<Self::ContractEmitted, Self::Err>>, Self::Err>,
     > + Send;
 
-    fn write<Ev: Decode + 'static, Args: Encode + Sync + Send>(
+    fn write<Ev: Decode + Event + 'static, Args: Encode + Sync + Send>(
         &self,
         address: <Self::C as Config>::AccountId,
         message: &str,
@@ -48,12 +54,108 @@ pub trait ContractClient {
         value: <Self::E as Environment>::Balance,
     ) -> impl Future<Output = Result<Ev, Self::Err>> + Send;
 
-    fn decode_event<Ev: Decode>(&self, mut ev_data: &[u8]) -> Result<Ev, Self::Err> {
-        let result = <Ev as Decode>::decode(&mut ev_data)?;
+    /// Like `write`, but returns every `Ev` the call emitted, in order, for messages that emit
+    /// one event per item of a batch.
+    fn write_batch<Ev: Decode + Event + 'static, Args: Encode + Sync + Send>(
+        &self,
+        address: <Self::C as Config>::AccountId,
+        message: &str,
+        args: &Args,
+        value: <Self::E as Environment>::Balance,
+    ) -> impl Future<Output = Result<Vec<Ev>, Self::Err>> + Send;
+
+    fn read<D: Decode + 'static, Args: Encode + Sync + Send>(
+        &self,
+        address: <Self::C as Config>::AccountId,
+        message: &str,
+        args: &Args,
+    ) -> impl Future<Output = Result<D, Self::Err>> + Send;
+
+    /// Job `job_id` of the catalog at `address`, read through the node's catalog runtime api.
+    fn job(
+        &self,
+        address: <Self::C as Config>::AccountId,
+        job_id: HashId,
+    ) -> impl Future<Output = Result<Option<JobRecord>, Self::Err>> + Send;
+
+    /// Registration, reputation and stake of `who` in the catalog at `address`, read through the
+    /// node's catalog runtime api.
+    fn worker_profile(
+        &self,
+        address: <Self::C as Config>::AccountId,
+        who: <Self::C as Config>::AccountId,
+    ) -> impl Future<Output = Result<CatalogWorker, Self::Err>> + Send;
+
+    /// Calls the message with `selector` on a contract the client has no metadata for, such as
+    /// a PSP22 token, failing if the call reverts.
+    fn write_selector<Args: Encode + Sync + Send>(
+        &self,
+        address: <Self::C as Config>::AccountId,
+        selector: [u8; 4],
+        args: &Args,
+        value: <Self::E as Environment>::Balance,
+    ) -> impl Future<Output = Result<(), Self::Err>> + Send;
+
+    /// Account the client signs its transactions with.
+    fn account_id(&self) -> <Self::C as Config>::AccountId;
+
+    /// SCALE encoded signature of `payload` by `account_id`, checked with `verify_signature`.
+    fn sign(&self, payload: &[u8]) -> Vec<u8>;
+
+    /// Decodes `ev` as an `Ev`, failing if it was emitted with a different signature topic.
+    fn decode_event<Ev: Decode + Event>(
+        &self,
+        ev: &Self::ContractEmitted,
+    ) -> Result<Ev, Self::Err> {
+        if !matches_signature::<Ev>(ev.topics_ref()) {
+            return Err(codec::Error::from("Event signature topic does not match").into());
+        }
+
+        let result = <Ev as DecodeAll>::decode_all(&mut ev.data_ref())?;
         Ok(result)
     }
 }
 
+/// A worker's standing in the catalog, as returned by the `CatalogApi_worker_profile` runtime api.
+#[derive(Debug, Decode)]
+pub struct CatalogWorker {
+    /// Capabilities the worker registered with, or `None` if it isn't registered.
+    pub profile: Option<WorkerProfile>,
+    pub reputation: Reputation,
+    pub stake: Option<Stake>,
+}
+
+/// Whether `signature`, as returned by `ContractClient::sign`, was made over `payload` by the
+/// SCALE encoded account `who`.
+pub fn verify_signature(payload: &[u8], mut signature: &[u8], mut who: &[u8]) -> bool {
+    let (Ok(signature), Ok(who)) = (
+        <sp_runtime::MultiSignature as Decode>::decode(&mut signature),
+        <sp_runtime::AccountId32 as Decode>::decode(&mut who),
+    ) else {
+        return false;
+    };
+
+    signature.verify(payload, &who)
+}
+
+/// Decodes, in the order they were emitted, the events of `contract_events` emitted as an `Ev`.
+fn decode_batch<Ev: Decode + Event>(
+    contract_events: impl IntoIterator<Item = ContractEvent>,
+) -> Result<Vec<Ev>, codec::Error> {
+    contract_events
+        .into_iter()
+        .filter(|contract_event| matches_signature::<Ev>(contract_event.topics_ref()))
+        .map(|contract_event| <Ev as Decode>::decode(&mut contract_event.data_ref()))
+        .collect()
+}
+
+fn matches_signature<Ev: Event>(topics: &[[u8; 32]]) -> bool {
+    match Ev::SIGNATURE_TOPIC {
+        Some(signature) => topics.first() == Some(&signature),
+        None => true,
+    }
+}
+
 pub struct Client<'a, C, E, S> {
     ink_project: InkProject,
     signer: &'a S,
@@ -81,7 +183,7 @@ where
     type C = C;
     type E = E;
     type Err = ContractClientError;
-    type ContractEmitted = ContractEmitted;
+    type ContractEmitted = ContractEvent;
 
     async fn contract_event_sub(
         &self,
@@ -104,19 +206,25 @@ where
             .try_filter_map(|ext: ExtrinsicDetails<C, OnlineClient<C>>| async move {
                 match ext.events().await {
                     Ok(ev) => Ok(Some(iter(
-                        ev.find::<ContractEmitted>()
+                        ev.iter()
+                            .map(|details| ContractEvent::from_details(&details?))
+                            .filter_map(Result::transpose)
                             .map(|r| r.map_err(|e| <Self as ContractClient>::Err::from(e)))
-                            .collect::<Vec<Result<ContractEmitted, Self::Err>>>(),
+                            .collect::<Vec<Result<ContractEvent, Self::Err>>>(),
                     ))),
                     Err(err) => Err(err),
                 }
             })
             .try_flatten()
             .try_filter_map({
-                move |ev: ContractEmitted| {
+                move |ev: ContractEvent| {
                     let addr = addr.clone();
                     async move {
-                        let res = if ev.contract == addr { Some(ev) } else { None };
+                        let res = if ev.contract_emitted.contract == addr {
+                            Some(ev)
+                        } else {
+                            None
+                        };
                         Ok(res)
                     }
                 }
@@ -125,39 +233,137 @@ where
         Ok(contract_event_stream)
     }
 
-    async fn write<Ev: Decode, Args: Encode + Sync + Send>(
+    async fn write<Ev: Decode + Event, Args: Encode + Sync + Send>(
         &self,
         address: <C as Config>::AccountId,
         message: &str,
         args: &Args,
         value: <Self::E as Environment>::Balance,
     ) -> Result<Ev, ContractClientError> {
-        let message = self.ink_project.get_message(message)?;
-        let mut data = message.get_selector()?;
-        args.encode_to(&mut data);
+        let events = self
+            .submit_call(address, message, args, value.into())
+            .await?;
 
-        let gas_limit = self
-            .call(address.clone(), message.get_label(), &args)
+        let contract_event = events
+            .iter()
+            .map(|details| ContractEvent::from_details(&details?))
+            .filter_map(Result::transpose)
+            .find(|ev| {
+                ev.as_ref()
+                    .map_or(true, |ev| matches_signature::<Ev>(ev.topics_ref()))
+            })
+            .ok_or_else(|| Error::EventNotFound)??;
+
+        let result = <Ev as Decode>::decode(&mut contract_event.data_ref())?;
+
+        Ok(result)
+    }
+
+    async fn write_batch<Ev: Decode + Event, Args: Encode + Sync + Send>(
+        &self,
+        address: <C as Config>::AccountId,
+        message: &str,
+        args: &Args,
+        value: <Self::E as Environment>::Balance,
+    ) -> Result<Vec<Ev>, ContractClientError> {
+        let events = self
+            .submit_call(address, message, args, value.into())
+            .await?;
+
+        let mut contract_events = Vec::new();
+        for details in events.iter() {
+            if let Some(contract_event) = ContractEvent::from_details(&details?)? {
+                contract_events.push(contract_event);
+            }
+        }
+
+        let results = decode_batch::<Ev>(contract_events)?;
+
+        Ok(results)
+    }
+
+    async fn read<D: Decode, Args: Encode + Sync + Send>(
+        &self,
+        address: <C as Config>::AccountId,
+        message: &str,
+        args: &Args,
+    ) -> Result<D, ContractClientError> {
+        let exec_return = self
+            .call(address, message, args)
             .await?
-            .gas_required;
+            .result
+            .map_err(Error::from)?;
+
+        let result =
+            <MessageResult<D>>::decode(&mut exec_return.data.as_slice())?.map_err(Error::from)?;
+
+        Ok(result)
+    }
+
+    async fn job(
+        &self,
+        address: <C as Config>::AccountId,
+        job_id: HashId,
+    ) -> Result<Option<JobRecord>, ContractClientError> {
+        let catalog: AccountId32 = address.into();
+        let params = (Some(catalog), job_id).encode();
+        let record = self
+            .query_catalog("CatalogApi_catalog_job", &params)
+            .await?;
+
+        Ok(record)
+    }
+
+    async fn worker_profile(
+        &self,
+        address: <C as Config>::AccountId,
+        who: <C as Config>::AccountId,
+    ) -> Result<CatalogWorker, ContractClientError> {
+        let (catalog, who): (AccountId32, AccountId32) = (address.into(), who.into());
+        let params = (Some(catalog), who).encode();
+        let worker = self
+            .query_catalog("CatalogApi_worker_profile", &params)
+            .await?;
+
+        Ok(worker)
+    }
+
+    async fn write_selector<Args: Encode + Sync + Send>(
+        &self,
+        address: <C as Config>::AccountId,
+        selector: [u8; 4],
+        args: &Args,
+        value: <Self::E as Environment>::Balance,
+    ) -> Result<(), ContractClientError> {
+        let mut data = selector.to_vec();
+        args.encode_to(&mut data);
+
+        let dry_run = self.dry_run(address.clone(), data.clone()).await?;
+        let exec_return = dry_run.result.map_err(Error::from)?;
+
+        if exec_return.did_revert() {
+            return Err(Error::CallReverted.into());
+        }
 
         let call_tx = chain::tx().contracts().call(
-            address.clone().into(),
+            address.into(),
             value.into(),
-            gas_limit.into(),
+            dry_run.gas_required.into(),
             None,
             data,
         );
 
-        let events = self.submit_extrinsic(call_tx).await?;
+        self.submit_extrinsic(call_tx).await?;
 
-        let contract_emitted = events
-            .find_first::<ContractEmitted>()?
-            .ok_or_else(|| Error::EventNotFound)?;
+        Ok(())
+    }
 
-        let result = <Ev as Decode>::decode(&mut contract_emitted.data.as_slice())?;
+    fn account_id(&self) -> <C as Config>::AccountId {
+        self.signer.account_id()
+    }
 
-        Ok(result)
+    fn sign(&self, payload: &[u8]) -> Vec<u8> {
+        self.signer.sign(payload).encode()
     }
 }
 
@@ -186,18 +392,20 @@ where
         })
     }
 
-    pub async fn instantiate(
+    pub async fn instantiate<Args: Encode>(
         &self,
         constructor: &str,
+        args: &Args,
         value: <E as Environment>::Balance,
     ) -> Result<AccountId32, Error> {
         let salt = rand::random::<[u8; 8]>().to_vec();
         let code = self.ink_project.code()?;
 
-        let data = self
+        let mut data = self
             .ink_project
             .get_constructor(constructor)?
             .get_selector()?;
+        args.encode_to(&mut data);
 
         let gas_limit = self
             .estimate_gas_instantiate(
@@ -222,19 +430,6 @@ where
         Ok(instantiated.contract)
     }
 
-    pub async fn read<D: Decode, Args: Encode + Clone>(
-        &self,
-        address: <C as Config>::AccountId,
-        message: &str,
-        args: Args,
-    ) -> Result<D, Error> {
-        let exec_return = self.call(address, message, &args).await?.result?;
-
-        let result = <MessageResult<D>>::decode(&mut exec_return.data.as_slice())??;
-
-        Ok(result)
-    }
-
     pub async fn read_storage<D: Decode>(
         &self,
         contract_address: C::AccountId,
@@ -262,6 +457,16 @@ where
         Ok(data)
     }
 
+    /// Address of the catalog the node deployed at genesis, if it deployed one.
+    pub async fn catalog_address(&self) -> Result<Option<AccountId32>, Error> {
+        let address = self
+            .rpc_client
+            .request("bruja_catalogAddress", rpc_params![])
+            .await?;
+
+        Ok(address)
+    }
+
     pub async fn online_client(&self) -> Result<OnlineClient<C>, ContractClientError> {
         let client = OnlineClient::<C>::from_rpc_client(self.rpc_client.clone()).await?;
 
@@ -292,6 +497,30 @@ where
         Ok(gas_consumed.into())
     }
 
+    async fn submit_call<Args: Encode>(
+        &self,
+        address: <C as Config>::AccountId,
+        message: &str,
+        args: &Args,
+        value: u128,
+    ) -> Result<ExtrinsicEvents<C>, Error> {
+        let message = self.ink_project.get_message(message)?;
+        let mut data = message.get_selector()?;
+        args.encode_to(&mut data);
+
+        let gas_limit = self
+            .call(address.clone(), message.get_label(), &args)
+            .await?
+            .gas_required;
+
+        let call_tx =
+            chain::tx()
+                .contracts()
+                .call(address.into(), value, gas_limit.into(), None, data);
+
+        self.submit_extrinsic(call_tx).await
+    }
+
     async fn call<Args: Encode>(
         &self,
         address: <C as Config>::AccountId,
@@ -304,6 +533,14 @@ where
 
         args.encode_to(&mut input_data);
 
+        self.dry_run(address, input_data).await
+    }
+
+    async fn dry_run(
+        &self,
+        address: <C as Config>::AccountId,
+        input_data: Vec<u8>,
+    ) -> Result<ContractExecResult<E::Balance, ()>, Error> {
         let params = Call::new(
             self.signer.account_id(),
             address,
@@ -325,6 +562,18 @@ where
         Ok(contract_result)
     }
 
+    /// Calls the catalog runtime api `function`, decoding the catalog's value it returns.
+    async fn query_catalog<R: Decode>(&self, function: &str, params: &[u8]) -> Result<R, Error> {
+        let encoded = self
+            .call_runtime_api::<Option<Vec<u8>>>(function, Some(params), None)
+            .await?
+            .ok_or(Error::CatalogUnavailable)?;
+
+        let value = R::decode(&mut encoded.as_slice())?;
+
+        Ok(value)
+    }
+
     async fn call_runtime_api<R: Decode>(
         &self,
         function: &str,
@@ -434,8 +683,14 @@ pub enum Error {
     #[error("")]
     EventNotFound,
 
+    #[error("Contract call reverted")]
+    CallReverted,
+
     #[error("No data found at provided storage key")]
     StorageEntryIsEmpty,
+
+    #[error("Unable to query the catalog through the runtime api")]
+    CatalogUnavailable,
 }
 
 impl From<LangError> for Error {
@@ -552,15 +807,47 @@ impl From<sp_weights::Weight> for Weight {
 pub trait ContractEmittedT {
     fn data_ref(&self) -> &[u8];
     fn data(self) -> Vec<u8>;
+    fn topics_ref(&self) -> &[[u8; 32]];
+}
+
+/// `ContractEmitted` event along with the topics the contract deposited it with. The first topic
+/// of an ink event is the signature topic that identifies its type.
+#[derive(Debug)]
+pub struct ContractEvent {
+    contract_emitted: ContractEmitted,
+    topics: Vec<[u8; 32]>,
 }
 
-impl ContractEmittedT for ContractEmitted {
+impl ContractEvent {
+    fn from_details<C: Config>(details: &EventDetails<C>) -> Result<Option<Self>, subxt::Error> {
+        let Some(contract_emitted) = details.as_event::<ContractEmitted>()? else {
+            return Ok(None);
+        };
+
+        let topics = details
+            .topics()
+            .iter()
+            .map(|topic| <[u8; 32]>::decode(&mut topic.encode().as_slice()))
+            .collect::<Result<Vec<[u8; 32]>, codec::Error>>()?;
+
+        Ok(Some(Self {
+            contract_emitted,
+            topics,
+        }))
+    }
+}
+
+impl ContractEmittedT for ContractEvent {
     fn data_ref(&self) -> &[u8] {
-        self.data.as_slice()
+        self.contract_emitted.data.as_slice()
     }
 
     fn data(self) -> Vec<u8> {
-        self.data
+        self.contract_emitted.data
+    }
+
+    fn topics_ref(&self) -> &[[u8; 32]] {
+        &self.topics
     }
 }
 
@@ -568,12 +855,66 @@ impl ContractEmittedT for ContractEmitted {
 mod test {
     use super::*;
 
+    use catalog::catalog::{JobRequestSubmitted, JobRequirements};
     use ink::env::DefaultEnvironment;
     use std::{any::Any, collections::HashMap, marker::Send, sync::Mutex};
     use subxt::SubstrateConfig;
 
     use crate::services::test::Expectation;
 
+    fn job_request_submitted(id: [u8; 32], nonce: u64) -> ContractEvent {
+        let event = JobRequestSubmitted {
+            who: [1; 32].into(),
+            id,
+            nonce,
+            value: 100,
+            deadline: 0,
+            timeout: 0,
+            replicas: 1,
+            requirements: JobRequirements::default(),
+            bidding_ends: None,
+            token: None,
+            allowed_workers: vec![],
+            milestones: 1,
+        };
+
+        ContractEvent {
+            contract_emitted: ContractEmitted {
+                contract: AccountId32([0; 32]),
+                data: event.encode(),
+            },
+            topics: vec![
+                <JobRequestSubmitted as Event>::SIGNATURE_TOPIC.unwrap(),
+                [1; 32],
+                id,
+            ],
+        }
+    }
+
+    #[test]
+    fn decode_batch_returns_id_of_every_submitted_job_in_order() {
+        let other_event = ContractEvent {
+            contract_emitted: ContractEmitted {
+                contract: AccountId32([0; 32]),
+                data: vec![0; 8],
+            },
+            topics: vec![[9; 32]],
+        };
+        let contract_events = vec![
+            job_request_submitted([2; 32], 0),
+            other_event,
+            job_request_submitted([3; 32], 1),
+        ];
+
+        let ids = decode_batch::<JobRequestSubmitted>(contract_events)
+            .unwrap()
+            .iter()
+            .map(JobRequestSubmitted::id)
+            .collect::<Vec<[u8; 32]>>();
+
+        assert_eq!(ids, vec![[2; 32], [3; 32]]);
+    }
+
     struct MockContractClient {
         expectations: Mutex<HashMap<String, Box<dyn Any>>>,
     }
@@ -581,9 +922,9 @@ mod test {
     impl MockContractClient {
         fn _expect_contract_event_sub(
             &mut self,
-        ) -> &mut Expectation<Result<Vec<Result<ContractEmitted, subxt::Error>>, ContractClientError>>
+        ) -> &mut Expectation<Result<Vec<Result<ContractEvent, subxt::Error>>, ContractClientError>>
         {
-            self._expectation::<Result<Vec<Result<ContractEmitted, subxt::Error>>, ContractClientError>>("contract_event_sub")
+            self._expectation::<Result<Vec<Result<ContractEvent, subxt::Error>>, ContractClientError>>("contract_event_sub")
         }
 
         fn _expect_write<Ev: Decode + 'static>(
@@ -592,6 +933,42 @@ mod test {
             self._expectation::<Result<Ev, ContractClientError>>("write")
         }
 
+        fn _expect_write_batch<Ev: Decode + 'static>(
+            &mut self,
+        ) -> &mut Expectation<Result<Vec<Ev>, ContractClientError>> {
+            self._expectation::<Result<Vec<Ev>, ContractClientError>>("write_batch")
+        }
+
+        fn _expect_read<D: Decode + 'static>(
+            &mut self,
+        ) -> &mut Expectation<Result<D, ContractClientError>> {
+            self._expectation::<Result<D, ContractClientError>>("read")
+        }
+
+        fn _expect_job(
+            &mut self,
+        ) -> &mut Expectation<Result<Option<JobRecord>, ContractClientError>> {
+            self._expectation::<Result<Option<JobRecord>, ContractClientError>>("job")
+        }
+
+        fn _expect_worker_profile(
+            &mut self,
+        ) -> &mut Expectation<Result<CatalogWorker, ContractClientError>> {
+            self._expectation::<Result<CatalogWorker, ContractClientError>>("worker_profile")
+        }
+
+        fn _expect_write_selector(&mut self) -> &mut Expectation<Result<(), ContractClientError>> {
+            self._expectation::<Result<(), ContractClientError>>("write_selector")
+        }
+
+        fn _expect_account_id(&mut self) -> &mut Expectation<AccountId32> {
+            self._expectation::<AccountId32>("account_id")
+        }
+
+        fn _expect_sign(&mut self) -> &mut Expectation<Vec<u8>> {
+            self._expectation::<Vec<u8>>("sign")
+        }
+
         fn _expectation<T: 'static>(&mut self, entry: &str) -> &mut Expectation<T> {
             self.expectations
                 .get_mut()
@@ -620,7 +997,7 @@ mod test {
         type C = SubstrateConfig;
         type E = DefaultEnvironment;
         type Err = ContractClientError;
-        type ContractEmitted = ContractEmitted;
+        type ContractEmitted = ContractEvent;
 
         fn contract_event_sub(
             &self,
@@ -632,7 +1009,7 @@ mod test {
             >,
         > + Send {
             let expectation = self
-                .into_expectation::<Result<Vec<Result<ContractEmitted, ContractClientError>>, ContractClientError>>(
+                .into_expectation::<Result<Vec<Result<ContractEvent, ContractClientError>>, ContractClientError>>(
                     "publish_message",
                 );
             let res = expectation.func().unwrap()().map(|i| iter(i.into_iter()));
@@ -640,7 +1017,7 @@ mod test {
             async { res }
         }
 
-        fn write<Ev: Decode + 'static, Args: Encode + Sync + Send>(
+        fn write<Ev: Decode + Event + 'static, Args: Encode + Sync + Send>(
             &self,
             _address: <Self::C as Config>::AccountId,
             _message: &str,
@@ -652,9 +1029,73 @@ mod test {
             async move { func() }
         }
 
-        fn decode_event<Ev: Decode>(&self, mut ev_data: &[u8]) -> Result<Ev, Self::Err> {
-            let result = <Ev as Decode>::decode(&mut ev_data)?;
-            Ok(result)
+        fn write_batch<Ev: Decode + Event + 'static, Args: Encode + Sync + Send>(
+            &self,
+            _address: <Self::C as Config>::AccountId,
+            _message: &str,
+            _args: &Args,
+            _value: <Self::E as Environment>::Balance,
+        ) -> impl Future<Output = Result<Vec<Ev>, Self::Err>> + Send {
+            let expectation =
+                self.into_expectation::<Result<Vec<Ev>, ContractClientError>>("write_batch");
+            let func = expectation.func().unwrap();
+            async move { func() }
+        }
+
+        fn read<D: Decode + 'static, Args: Encode + Sync + Send>(
+            &self,
+            _address: <Self::C as Config>::AccountId,
+            _message: &str,
+            _args: &Args,
+        ) -> impl Future<Output = Result<D, Self::Err>> + Send {
+            let expectation = self.into_expectation::<Result<D, ContractClientError>>("read");
+            let func = expectation.func().unwrap();
+            async move { func() }
+        }
+
+        fn job(
+            &self,
+            _address: <Self::C as Config>::AccountId,
+            _job_id: HashId,
+        ) -> impl Future<Output = Result<Option<JobRecord>, Self::Err>> + Send {
+            let expectation =
+                self.into_expectation::<Result<Option<JobRecord>, ContractClientError>>("job");
+            let func = expectation.func().unwrap();
+            async move { func() }
+        }
+
+        fn worker_profile(
+            &self,
+            _address: <Self::C as Config>::AccountId,
+            _who: <Self::C as Config>::AccountId,
+        ) -> impl Future<Output = Result<CatalogWorker, Self::Err>> + Send {
+            let expectation = self
+                .into_expectation::<Result<CatalogWorker, ContractClientError>>("worker_profile");
+            let func = expectation.func().unwrap();
+            async move { func() }
+        }
+
+        fn write_selector<Args: Encode + Sync + Send>(
+            &self,
+            _address: <Self::C as Config>::AccountId,
+            _selector: [u8; 4],
+            _args: &Args,
+            _value: <Self::E as Environment>::Balance,
+        ) -> impl Future<Output = Result<(), Self::Err>> + Send {
+            let expectation =
+                self.into_expectation::<Result<(), ContractClientError>>("write_selector");
+            let func = expectation.func().unwrap();
+            async move { func() }
+        }
+
+        fn account_id(&self) -> <Self::C as Config>::AccountId {
+            let expectation = self.into_expectation::<AccountId32>("account_id");
+            expectation.func().unwrap()()
+        }
+
+        fn sign(&self, _payload: &[u8]) -> Vec<u8> {
+            let expectation = self.into_expectation::<Vec<u8>>("sign");
+            expectation.func().unwrap()()
         }
     }
 }
@@ -7,6 +7,7 @@ use std::{fmt, string::FromUtf8Error};
 pub trait JobT: Encode + Decode {
     fn code_ref(&self) -> &[u8];
     fn params_ref(&self) -> &Vec<Vec<u8>>;
+    fn func_name_ref(&self) -> &[u8];
     fn func_name_string(&self) -> Result<String, FromUtf8Error>;
     fn into_parts(self) -> (Vec<u8>, Vec<Vec<u8>>, Vec<u8>);
     fn from_parts(code: Vec<u8>, params: Vec<Vec<u8>>, func_name: Vec<u8>) -> Self;
@@ -39,6 +40,10 @@ impl JobT for Job {
         &self.params
     }
 
+    fn func_name_ref(&self) -> &[u8] {
+        self.func_name.as_slice()
+    }
+
     fn func_name_string(&self) -> Result<String, FromUtf8Error> {
         let string = String::from_utf8(self.func_name.clone())?;
         Ok(string)
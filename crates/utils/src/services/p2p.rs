@@ -31,7 +31,7 @@ use tracing::{error, info, info_span, Instrument};
 pub trait NetworkClient {
     type Err;
     type Id;
-    type NetworkId: NetworkIdT + Display;
+    type NetworkId: NetworkIdT + Display + PartialEq;
     type GossipMessage: GossipMessageT<NetworkId = Self::NetworkId>;
     type Request: RequestT<Id = Self::Id>;
     type Response: ResponseT<Id = Self::Id>;